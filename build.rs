@@ -0,0 +1,25 @@
+fn main() {
+    // With the `ffi` feature, generates the C header from `src/api/ffi.rs` into `OUT_DIR` and checks the
+    // committed `include/hj.h` against it. The source tree is never written, so read-only checkouts build.
+    #[cfg(feature = "ffi")]
+    {
+        println!("cargo:rerun-if-changed=src/api/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        println!("cargo:rerun-if-changed=include/hj.h");
+
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let out_path = format!("{}/hj.h", std::env::var("OUT_DIR").unwrap());
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).expect("cbindgen.toml should be valid.");
+        cbindgen::Builder::new()
+            .with_crate(&crate_dir)
+            .with_config(config)
+            .generate()
+            .expect("The C header should generate.")
+            .write_to_file(&out_path);
+
+        let generated = std::fs::read_to_string(&out_path).expect("The generated header should be readable.");
+        if std::fs::read_to_string(format!("{}/include/hj.h", crate_dir)).ok().as_deref() != Some(generated.as_str()) {
+            println!("cargo:warning=include/hj.h is out of date; update it with `cp {} include/hj.h`.", out_path);
+        }
+    }
+}
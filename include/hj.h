@@ -0,0 +1,174 @@
+/*
+ * C interface to the hj game engine. Build with `cargo build --release --features ffi` and link against libhj.
+ *
+ * Every function catches its own errors and panics: failures are reported through `HJStatus`
+ * (or a null game from the constructors), and `hj_last_error` explains the most recent one.
+ * Pointers to games must come from `hj_game_new` or `hj_game_new_with_hands` and must not be used after
+ * `hj_game_free`. Output buffers must hold at least `capacity` items. A game must not be used from two
+ * threads at once.
+ */
+
+#ifndef HJ_H
+#define HJ_H
+
+/* Generated by cbindgen from src/api/ffi.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Functions take the direction as a plain `uint32_t`, so a value outside this enum is reported instead of trusted.
+ */
+typedef enum HJPassDirection {
+  HJ_PASS_DIRECTION_LEFT = 0,
+  HJ_PASS_DIRECTION_RIGHT,
+  HJ_PASS_DIRECTION_CROSS,
+  HJ_PASS_DIRECTION_NONE,
+} HJPassDirection;
+
+typedef enum HJPhase {
+  HJ_PHASE_PASSING = 0,
+  HJ_PHASE_PLAYING,
+  HJ_PHASE_FINISHED,
+  HJ_PHASE_FAILED,
+} HJPhase;
+
+/**
+ * Returned by every function that can fail. `hj_last_error` describes the most recent failure.
+ */
+typedef enum HJStatus {
+  HJ_STATUS_OK = 0,
+  HJ_STATUS_NULL_ARGUMENT,
+  HJ_STATUS_INVALID_ARGUMENT,
+  HJ_STATUS_INVALID_CARD,
+  HJ_STATUS_INVALID_SPEC,
+  HJ_STATUS_INVALID_DEAL,
+  HJ_STATUS_ACTOR_ERROR,
+  HJ_STATUS_PROTOCOL_ERROR,
+  HJ_STATUS_WRONG_PHASE,
+  HJ_STATUS_BUFFER_TOO_SMALL,
+  HJ_STATUS_PANIC,
+  HJ_STATUS_OUTPUT_ERROR,
+} HJStatus;
+
+/**
+ * A game in progress. Seats without a bot are played through `hj_game_pass` and `hj_game_play`.
+ */
+typedef struct HJGame HJGame;
+
+/**
+ * A card, with `rank` from 2 to 14 (ace) and `suit` 1 = spades, 2 = clubs, 3 = diamonds, 4 = hearts.
+ */
+typedef struct HJCard {
+  uint8_t rank;
+  uint8_t suit;
+} HJCard;
+
+typedef struct HJMove {
+  size_t seat;
+  struct HJCard card;
+} HJMove;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Describes the most recent failure on this thread. The string stays valid until the next call into the library.
+ */
+const char *hj_last_error(void);
+
+/**
+ * Deals a new game from `seed`, passing in `direction`, one of the `HJPassDirection` values. `bots` is null or an array of four bot specs, as on the command line
+ * (e.g. `rule_v1`, `mcts_v1:50:10`, `json:./bot`); seats with a null spec are played through `hj_game_pass` and
+ * `hj_game_play`. Returns null on failure.
+ */
+struct HJGame *hj_game_new(uint64_t seed,
+                           uint32_t direction,
+                           const char *const *bots);
+
+/**
+ * Like `hj_game_new`, but starts from 52 cards: 13 for seat 0, then 13 for seat 1, and so on.
+ */
+struct HJGame *hj_game_new_with_hands(const struct HJCard *cards,
+                                      uint32_t direction,
+                                      const char *const *bots);
+
+/**
+ * Frees a game and stops its bots. Passing null is allowed.
+ */
+void hj_game_free(struct HJGame *game);
+
+/**
+ * Play starts once every seat without a bot has passed. A bot that fails while passing or playing cannot be
+ * retried: the game moves to `HJ_PHASE_FAILED` and every later pass or play returns `HJ_STATUS_WRONG_PHASE`
+ * with the bot's error in `hj_last_error`.
+ */
+enum HJStatus hj_game_phase(const struct HJGame *game,
+                            enum HJPhase *out_phase);
+
+/**
+ * The seat that plays the next card.
+ */
+enum HJStatus hj_game_current_seat(const struct HJGame *game, size_t *out_seat);
+
+/**
+ * Writes the cards in a seat's hand. `out_len` is always set to the number of cards, even when the buffer is too small.
+ */
+enum HJStatus hj_game_hand(const struct HJGame *game,
+                           size_t seat,
+                           struct HJCard *out,
+                           size_t capacity,
+                           size_t *out_len);
+
+/**
+ * Writes the cards the current seat is allowed to play.
+ */
+enum HJStatus hj_game_legal_cards(const struct HJGame *game,
+                                  struct HJCard *out,
+                                  size_t capacity,
+                                  size_t *out_len);
+
+/**
+ * Writes the moves of the unfinished trick.
+ */
+enum HJStatus hj_game_current_trick(const struct HJGame *game,
+                                    struct HJMove *out,
+                                    size_t capacity,
+                                    size_t *out_len);
+
+/**
+ * Passes cards for a seat without a bot. The last of these passes also makes the bots pass; if one of them fails,
+ * its error is returned and the game is over, as `hj_game_phase` describes.
+ */
+enum HJStatus hj_game_pass(struct HJGame *game,
+                           size_t seat,
+                           const struct HJCard *cards,
+                           size_t len);
+
+/**
+ * Plays a card for the current seat, which must not have a bot. Illegal cards are rejected and leave the game unchanged.
+ */
+enum HJStatus hj_game_play(struct HJGame *game,
+                           struct HJCard card);
+
+/**
+ * Lets the bot in the current seat play a card, and writes its move to `out_move` if that is not null. If the bot
+ * fails, its error is returned and the game is over, as `hj_game_phase` describes.
+ */
+enum HJStatus hj_game_advance(struct HJGame *game,
+                              struct HJMove *out_move);
+
+/**
+ * Writes the four scores: the points taken so far, or the final result once the game is finished.
+ */
+enum HJStatus hj_game_scores(const struct HJGame *game, int64_t *out_scores);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* HJ_H */
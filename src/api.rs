@@ -1,2 +1,16 @@
+pub mod connection;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod engine;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod json;
+#[cfg(feature = "python")]
+pub mod python;
 pub mod rust;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod socket;
+pub mod supervisor;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod web;
@@ -0,0 +1,14 @@
+use std::time::Duration;
+
+use crate::game::errors::HJError;
+
+// A line-based channel to a bot speaking the JSON protocol, see `JSONActor`.
+pub trait BotConnection {
+    fn describe(&self) -> String;
+    fn send(&mut self, line: &str) -> Result<(), HJError>;
+    // Returns the next non-empty line, or an error if the bot goes away or takes too long.
+    fn receive(&mut self, timeout: Duration) -> Result<String, HJError>;
+    fn is_alive(&mut self) -> bool;
+    fn reconnect(&mut self) -> Result<(), HJError>;
+    fn shutdown(&mut self);
+}
@@ -0,0 +1,254 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Instant;
+
+use crate::api::json::RULE_VARIANT;
+use crate::game::data::{Card, Move, PassDirection};
+use crate::game::errors::HJError;
+use crate::rust_actors::actor_ismcts_v1::ActorISMCTSV1;
+use crate::rust_actors::actor_mcts_mod_v1::ActorMCTSModV1;
+use crate::rust_actors::actor_mcts_v1::ActorMCTSV1;
+use crate::rust_actors::player_state::BasicPlayerStateInterface;
+use crate::util::deck::find_winner_pidx;
+use crate::util::non_nan::NonNan;
+use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
+
+// Drives our searches from another program, one command per line:
+//   rules omnibus
+//   position pidx P hand CARD... [direction DIR] [passed CARD...] [received CARD...] [trick LEADER CARD CARD CARD CARD]... [current LEADER CARD...]
+//   go [engine mcts_v1|mcts_mod_v1|ismcts_v1|ismcts_mo_v1] [movetime MS] [iterations N] [slice MS] [infinite]
+//   stop | isready | quit
+// While searching, `info` lines report the value and visits per card after every slice; `bestcard` ends the search.
+pub fn run_engine() {
+    let (sender, lines) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            if line.as_ref().map_or(true, |l| sender.send(l.clone()).is_err()) { break; }
+        }
+    });
+
+    let mut engine = Engine { lines, pending: VecDeque::new(), position: None, quit: false };
+    while !engine.quit {
+        let line = match engine.pending.pop_front() {
+            Some(line) => line,
+            None => match engine.lines.recv() { Ok(line) => line, Err(_) => break },
+        };
+
+        if let Err(error) = engine.handle(&line) { reply(&format!("error {:?}", error)); }
+    }
+}
+
+fn reply(line: &str) {
+    let mut stdout = io::stdout().lock();
+    let _ = writeln!(stdout, "{}", line).and_then(|_| stdout.flush());
+}
+
+struct Engine {
+    lines: Receiver<String>,
+    pending: VecDeque<String>,
+    position: Option<Position>,
+    quit: bool,
+}
+
+impl Engine {
+    fn handle(&mut self, line: &str) -> Result<(), HJError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.first().copied() {
+            None | Some("stop") => {},
+            Some("isready") => reply("readyok"),
+            Some("quit") => self.quit = true,
+            Some("rules") if words.get(1) == Some(&RULE_VARIANT) => {},
+            Some("rules") => return Err(HJError::ProtocolError(format!("Only the {} rules are supported.", RULE_VARIANT))),
+            Some("position") => self.position = Some(Position::parse(&words[1..])?),
+            Some("go") => self.go(&words[1..])?,
+            Some(command) => return Err(HJError::ProtocolError(format!("Unknown command '{}'.", command))),
+        }
+
+        Ok(())
+    }
+
+    fn go(&mut self, words: &[&str]) -> Result<(), HJError> {
+        let position = self.position.as_ref().ok_or_else(|| HJError::ProtocolError("No position was set.".to_owned()))?;
+        let player_state = position.player_state()?;
+
+        let mut engine = "mcts_v1";
+        let mut movetime = None;
+        let mut iterations = None;
+        let mut slice = 50;
+        let mut infinite = false;
+        let mut args = words.iter();
+        while let Some(word) = args.next() {
+            let mut number = || args.next().and_then(|n| n.parse::<usize>().ok())
+                .ok_or_else(|| HJError::ProtocolError(format!("Expected a number after '{}'.", word)));
+            match *word {
+                "engine" => engine = args.next().copied().unwrap_or(""),
+                "movetime" => movetime = Some(number()?),
+                "iterations" => iterations = Some(number()?),
+                "slice" => slice = number()?.max(1),
+                "infinite" => infinite = true,
+                _ => return Err(HJError::ProtocolError(format!("Unknown go option '{}'.", word))),
+            }
+        }
+        if !infinite && movetime.is_none() && iterations.is_none() { movetime = Some(1000); }
+
+        let search: Box<dyn Fn() -> Vec<(Card, f32, usize)>> = match engine {
+            "mcts_v1" => {
+                let mut actor = ActorMCTSV1::new(slice, 1);
+                actor.set_player_state(player_state);
+                let current = position.current.clone();
+                Box::new(move || actor.search(&current, slice))
+            },
+            "mcts_mod_v1" => {
+                let mut actor = ActorMCTSModV1::new(slice);
+                actor.set_player_state(player_state);
+                let current = position.current.clone();
+                Box::new(move || actor.search(&current, slice))
+            },
+            "ismcts_v1" | "ismcts_mo_v1" => {
+                let mut actor = if engine == "ismcts_v1" { ActorISMCTSV1::new(slice) } else { ActorISMCTSV1::multi_observer(slice) };
+                actor.set_player_state(player_state);
+                let current = position.current.clone();
+                Box::new(move || actor.search(&current, slice))
+            },
+            _ => return Err(HJError::ProtocolError(format!("Unknown engine '{}'.", engine))),
+        };
+
+        let start = Instant::now();
+        let mut totals = HashMap::<Card, (f32, usize)>::new();
+        let mut done = 0;
+        loop {
+            // Unvisited cards are the ones that cannot legally be played.
+            for (card, value, visits) in search().into_iter().filter(|(_, _, visits)| *visits > 0) {
+                let entry = totals.entry(card).or_insert((0., 0));
+                entry.0 += value;
+                entry.1 += visits;
+            }
+            done += 1;
+
+            let mut cards: Vec<_> = totals.iter().collect();
+            cards.sort_by_key(|(_, (_, visits))| std::cmp::Reverse(*visits));
+            reply(&format!("info iterations {} time {}{}", done, start.elapsed().as_millis(), cards.iter().map(|(card, (value, visits))| {
+                format!(" card {} value {:.3} visits {}", card, average(*value, *visits), visits)
+            }).collect::<String>()));
+
+            let mut stop = movetime.is_some_and(|t| start.elapsed().as_millis() >= t as u128) || iterations.is_some_and(|n| done >= n);
+            while let Ok(line) = self.lines.try_recv() {
+                match line.trim() {
+                    "stop" => stop = true,
+                    "quit" => { stop = true; self.quit = true; },
+                    "isready" => reply("readyok"),
+                    _ => self.pending.push_back(line),
+                }
+            }
+            if stop { break; }
+        }
+
+        let best_card = totals.into_iter()
+            .max_by_key(|(_, (value, visits))| NonNan::new(average(*value, *visits)).unwrap())
+            .ok_or_else(|| HJError::ProtocolError("The search found no cards.".to_owned()))?.0;
+        reply(&format!("bestcard {}", best_card));
+        Ok(())
+    }
+}
+
+fn average(value: f32, visits: usize) -> f32 {
+    if visits == 0 { 0. } else { value / visits as f32 }
+}
+
+struct Position {
+    pidx: usize,
+    hand: Vec<Card>,
+    // Where the passed cards went; without it they are only known to be out of the hand.
+    direction: Option<PassDirection>,
+    passed: Vec<Card>,
+    received: Vec<Card>,
+    tricks: Vec<Vec<Move>>,
+    current: Vec<Move>,
+}
+
+impl Position {
+    fn parse(words: &[&str]) -> Result<Self, HJError> {
+        let invalid = |reason: String| HJError::ProtocolError(format!("{} in position '{}'.", reason, words.join(" ")));
+        let moves = |leader: usize, cards: &[Card]| cards.iter().enumerate().map(|(i, c)| Move((leader + i) % 4, *c)).collect::<Vec<Move>>();
+
+        let mut position = Self { pidx: 0, hand: vec![], direction: None, passed: vec![], received: vec![], tricks: vec![], current: vec![] };
+        let mut idx = 0;
+        while idx < words.len() {
+            let section = words[idx];
+            if section == "direction" {
+                let direction = words.get(idx + 1).and_then(|w| PassDirection::from_name(w));
+                position.direction = Some(direction.ok_or_else(|| invalid("Expected a pass direction after 'direction'".to_owned()))?);
+                idx += 2;
+                continue;
+            }
+            let leader = match section {
+                "pidx" | "trick" | "current" => {
+                    idx += 1;
+                    let leader = words.get(idx).and_then(|w| w.parse::<usize>().ok()).filter(|p| *p < 4);
+                    Some(leader.ok_or_else(|| invalid(format!("Expected a seat after '{}'", section)))?)
+                },
+                _ => None,
+            };
+            idx += 1;
+
+            let mut cards = vec![];
+            while let Some(card) = words.get(idx).and_then(|w| w.parse::<Card>().ok()) {
+                cards.push(card);
+                idx += 1;
+            }
+
+            match (section, leader) {
+                ("pidx", Some(pidx)) if cards.is_empty() => position.pidx = pidx,
+                ("hand", _) => position.hand = cards,
+                ("passed", _) => position.passed = cards,
+                ("received", _) => position.received = cards,
+                ("trick", Some(leader)) if cards.len() == 4 => position.tricks.push(moves(leader, &cards)),
+                ("current", Some(leader)) if cards.len() < 4 => position.current = moves(leader, &cards),
+                _ => return Err(invalid(format!("Unexpected '{}'", section))),
+            }
+        }
+
+        let to_play = position.current.first().map_or_else(
+            || position.tricks.last().map_or(position.pidx, find_winner_pidx),
+            |m| (m.pidx() + position.current.len()) % 4,
+        );
+        if to_play != position.pidx && !(position.tricks.is_empty() && position.current.is_empty()) {
+            return Err(invalid(format!("Seat {} is not to play", position.pidx)));
+        }
+
+        Ok(position)
+    }
+
+    // Replays the observation the way an actor would have seen it during the game.
+    fn player_state(&self) -> Result<ExtendedPlayerState, HJError> {
+        let mut player_state = ExtendedPlayerState::default();
+        player_state.set_pidx(self.pidx);
+        player_state.set_cards(self.hand.clone());
+
+        let missing = |card: &Card| HJError::ProtocolError(format!("Seat {} does not hold {}.", self.pidx, card));
+        if let Some(card) = self.passed.iter().find(|c| !player_state.cards().contains(c)) { return Err(missing(card)); }
+        match self.direction {
+            Some(direction) => player_state.update_did_pass(&self.passed, direction),
+            None => for card in &self.passed { player_state.update_did_play_card(card); },
+        }
+        player_state.update_end_pass(&self.received);
+
+        for trick in &self.tricks {
+            player_state.update_play_card(trick);
+            for Move(pidx, card) in trick {
+                if *pidx != self.pidx { continue; }
+                if !player_state.cards().contains(card) { return Err(missing(card)); }
+                player_state.update_did_play_card(card);
+            }
+            player_state.update_end_round(trick, find_winner_pidx(trick));
+        }
+        player_state.update_play_card(&self.current);
+
+        if player_state.cards().is_empty() {
+            return Err(HJError::ProtocolError(format!("Seat {} has no cards left to play.", self.pidx)));
+        }
+        Ok(player_state)
+    }
+}
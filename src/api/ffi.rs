@@ -0,0 +1,338 @@
+// The pointer rules are the same for every function and are spelled out once at the top of `include/hj.h`.
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::game::data::{Card, Hand, Move, PassDirection};
+use crate::game::errors::HJError;
+use crate::internal::session::{GameSession, Phase};
+use crate::rust_actors::util::get_allowed_cards;
+use crate::util::deck::get_seeded_hands;
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Returned by every function that can fail. `hj_last_error` describes the most recent failure.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HJStatus {
+    Ok = 0,
+    NullArgument,
+    InvalidArgument,
+    InvalidCard,
+    InvalidSpec,
+    InvalidDeal,
+    ActorError,
+    ProtocolError,
+    WrongPhase,
+    BufferTooSmall,
+    Panic,
+    OutputError,
+}
+
+/// Functions take the direction as a plain `uint32_t`, so a value outside this enum is reported instead of trusted.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HJPassDirection {
+    Left = 0,
+    Right,
+    Cross,
+    None,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HJPhase {
+    Passing = 0,
+    Playing,
+    Finished,
+    Failed,
+}
+
+/// A card, with `rank` from 2 to 14 (ace) and `suit` 1 = spades, 2 = clubs, 3 = diamonds, 4 = hearts.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct HJCard {
+    pub rank: u8,
+    pub suit: u8,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct HJMove {
+    pub seat: usize,
+    pub card: HJCard,
+}
+
+/// A game in progress. Seats without a bot are played through `hj_game_pass` and `hj_game_play`.
+pub struct HJGame(GameSession);
+
+struct Failure(HJStatus, String);
+
+impl From<HJError> for Failure {
+    fn from(error: HJError) -> Self {
+        match error {
+            HJError::InvalidCardError(message) => Failure(HJStatus::InvalidCard, message),
+            HJError::InvalidSpecError(message) => Failure(HJStatus::InvalidSpec, message),
+            HJError::InvalidDealError(message) => Failure(HJStatus::InvalidDeal, message),
+            HJError::ActorError(message) => Failure(HJStatus::ActorError, message),
+            HJError::ProtocolError(message) => Failure(HJStatus::ProtocolError, message),
+            HJError::WrongPhaseError(message) => Failure(HJStatus::WrongPhase, message),
+            HJError::OutputError(message) => Failure(HJStatus::OutputError, message),
+        }
+    }
+}
+
+fn set_last_error(message: &str) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+}
+
+// Runs `body` so that neither errors nor panics escape, and records what went wrong.
+fn guard<F: FnOnce() -> Result<(), Failure>>(body: F) -> HJStatus {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => HJStatus::Ok,
+        Ok(Err(Failure(status, message))) => { set_last_error(&message); status },
+        Err(_) => { set_last_error("Internal error; the game should no longer be used."); HJStatus::Panic },
+    }
+}
+
+fn null(name: &str) -> Failure {
+    Failure(HJStatus::NullArgument, format!("'{}' should not be null.", name))
+}
+
+unsafe fn game_ref<'a>(game: *const HJGame) -> Result<&'a HJGame, Failure> {
+    game.as_ref().ok_or_else(|| null("game"))
+}
+
+unsafe fn game_mut<'a>(game: *mut HJGame) -> Result<&'a mut HJGame, Failure> {
+    game.as_mut().ok_or_else(|| null("game"))
+}
+
+fn check_seat(seat: usize) -> Result<usize, Failure> {
+    if seat < 4 { Ok(seat) } else { Err(Failure(HJStatus::InvalidArgument, format!("There is no seat {}.", seat))) }
+}
+
+fn card_from_c(card: HJCard) -> Result<Card, Failure> {
+    match (num::FromPrimitive::from_u8(card.rank), num::FromPrimitive::from_u8(card.suit)) {
+        (Some(rank), Some(suit)) => Ok(Card(rank, suit)),
+        _ => Err(Failure(HJStatus::InvalidCard, format!("There is no card with rank {} and suit {}.", card.rank, card.suit))),
+    }
+}
+
+fn card_to_c(card: Card) -> HJCard {
+    HJCard { rank: card.rank() as u8, suit: card.suit() as u8 }
+}
+
+fn move_to_c(new_move: &Move) -> HJMove {
+    HJMove { seat: new_move.pidx(), card: card_to_c(new_move.card()) }
+}
+
+// Copies `items` to the caller's buffer, always reporting how many there are.
+unsafe fn write_out<T: Copy>(items: &[T], out: *mut T, capacity: usize, out_len: *mut usize) -> Result<(), Failure> {
+    if out_len.is_null() { return Err(null("out_len")); }
+    *out_len = items.len();
+    if items.len() > capacity {
+        return Err(Failure(HJStatus::BufferTooSmall, format!("Room for {} items is needed, got {}.", items.len(), capacity)));
+    }
+    if !items.is_empty() {
+        if out.is_null() { return Err(null("out")); }
+        ptr::copy_nonoverlapping(items.as_ptr(), out, items.len());
+    }
+    Ok(())
+}
+
+// Enums are only ever passed out to C. Coming in, any integer could arrive, and one that is not a variant
+// would be undefined behaviour as soon as it is an enum, so it is checked as a number first.
+fn pass_direction_from_c(direction: u32) -> Result<PassDirection, Failure> {
+    match direction {
+        d if d == HJPassDirection::Left as u32 => Ok(PassDirection::Left),
+        d if d == HJPassDirection::Right as u32 => Ok(PassDirection::Right),
+        d if d == HJPassDirection::Cross as u32 => Ok(PassDirection::Cross),
+        d if d == HJPassDirection::None as u32 => Ok(PassDirection::None),
+        _ => Err(Failure(HJStatus::InvalidArgument, format!("There is no pass direction {}.", direction))),
+    }
+}
+
+impl From<Phase> for HJPhase {
+    fn from(phase: Phase) -> Self {
+        match phase {
+            Phase::Passing => HJPhase::Passing,
+            Phase::Playing => HJPhase::Playing,
+            Phase::Finished => HJPhase::Finished,
+            Phase::Failed => HJPhase::Failed,
+        }
+    }
+}
+
+// Reads the optional array of four bot specs; null entries are seats played through this interface.
+unsafe fn bot_specs_from_c(bots: *const *const c_char) -> Result<[Option<String>; 4], Failure> {
+    if bots.is_null() { return Ok([None, None, None, None]); }
+
+    let mut specs = [None, None, None, None];
+    for (pidx, spec) in std::slice::from_raw_parts(bots, 4).iter().enumerate() {
+        if spec.is_null() { continue; }
+        let spec = CStr::from_ptr(*spec).to_str().map_err(|_| Failure(HJStatus::InvalidSpec, "Bot specs should be UTF-8.".to_owned()))?;
+        specs[pidx] = Some(spec.to_owned());
+    }
+    Ok(specs)
+}
+
+/// Describes the most recent failure on this thread. The string stays valid until the next call into the library.
+#[no_mangle]
+pub extern "C" fn hj_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ptr())
+}
+
+/// Deals a new game from `seed`, passing in `direction`, one of the `HJPassDirection` values. `bots` is null or an array of four bot specs, as on the command line
+/// (e.g. `rule_v1`, `mcts_v1:50:10`, `json:./bot`); seats with a null spec are played through `hj_game_pass` and
+/// `hj_game_play`. Returns null on failure.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_new(seed: u64, direction: u32, bots: *const *const c_char) -> *mut HJGame {
+    let mut game = ptr::null_mut();
+    guard(|| {
+        let direction = pass_direction_from_c(direction)?;
+        let new_game = HJGame(GameSession::new(get_seeded_hands(seed), direction, &bot_specs_from_c(bots)?)?);
+        game = Box::into_raw(Box::new(new_game));
+        Ok(())
+    });
+    game
+}
+
+/// Like `hj_game_new`, but starts from 52 cards: 13 for seat 0, then 13 for seat 1, and so on.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_new_with_hands(cards: *const HJCard, direction: u32, bots: *const *const c_char) -> *mut HJGame {
+    let mut game = ptr::null_mut();
+    guard(|| {
+        let direction = pass_direction_from_c(direction)?;
+        if cards.is_null() { return Err(null("cards")); }
+        let cards = std::slice::from_raw_parts(cards, 52).iter().map(|c| card_from_c(*c)).collect::<Result<Vec<Card>, Failure>>()?;
+
+        let mut seen = [[false; 13]; 4];
+        for card in &cards {
+            if seen[card.suit().to_index()][card.rank().to_index()] {
+                return Err(Failure(HJStatus::InvalidDeal, format!("{} is dealt twice.", card)));
+            }
+            seen[card.suit().to_index()][card.rank().to_index()] = true;
+        }
+
+        let hands = [0, 1, 2, 3].map(|pidx| Hand::new(cards[pidx * 13..(pidx + 1) * 13].to_vec()));
+        game = Box::into_raw(Box::new(HJGame(GameSession::new(hands, direction, &bot_specs_from_c(bots)?)?)));
+        Ok(())
+    });
+    game
+}
+
+/// Frees a game and stops its bots. Passing null is allowed.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_free(game: *mut HJGame) {
+    if game.is_null() { return; }
+    guard(|| { drop(Box::from_raw(game)); Ok(()) });
+}
+
+/// Play starts once every seat without a bot has passed. A bot that fails while passing or playing cannot be
+/// retried: the game moves to `HJ_PHASE_FAILED` and every later pass or play returns `HJ_STATUS_WRONG_PHASE`
+/// with the bot's error in `hj_last_error`.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_phase(game: *const HJGame, out_phase: *mut HJPhase) -> HJStatus {
+    guard(|| {
+        let phase: HJPhase = game_ref(game)?.0.phase().into();
+        // Written without reading, since the caller's memory need not hold a valid `HJPhase` yet.
+        if out_phase.is_null() { return Err(null("out_phase")); }
+        out_phase.write(phase);
+        Ok(())
+    })
+}
+
+/// The seat that plays the next card.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_current_seat(game: *const HJGame, out_seat: *mut usize) -> HJStatus {
+    guard(|| {
+        let seat = game_ref(game)?.0.game_info().current_pidx();
+        *out_seat.as_mut().ok_or_else(|| null("out_seat"))? = seat;
+        Ok(())
+    })
+}
+
+/// Writes the cards in a seat's hand. `out_len` is always set to the number of cards, even when the buffer is too small.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_hand(game: *const HJGame, seat: usize, out: *mut HJCard, capacity: usize, out_len: *mut usize) -> HJStatus {
+    guard(|| {
+        let hand = &game_ref(game)?.0.game_info().hands()[check_seat(seat)?];
+        write_out(&hand.cards().iter().map(|c| card_to_c(*c)).collect::<Vec<HJCard>>(), out, capacity, out_len)
+    })
+}
+
+/// Writes the cards the current seat is allowed to play.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_legal_cards(game: *const HJGame, out: *mut HJCard, capacity: usize, out_len: *mut usize) -> HJStatus {
+    guard(|| {
+        let session = &game_ref(game)?.0;
+        let cards = match session.phase() {
+            Phase::Playing => get_allowed_cards(session.game_info()),
+            _ => vec![],
+        };
+        write_out(&cards.into_iter().map(card_to_c).collect::<Vec<HJCard>>(), out, capacity, out_len)
+    })
+}
+
+/// Writes the moves of the unfinished trick.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_current_trick(game: *const HJGame, out: *mut HJMove, capacity: usize, out_len: *mut usize) -> HJStatus {
+    guard(|| {
+        let moves = game_ref(game)?.0.game_info().current_moves().iter().map(move_to_c).collect::<Vec<HJMove>>();
+        write_out(&moves, out, capacity, out_len)
+    })
+}
+
+/// Passes cards for a seat without a bot. The last of these passes also makes the bots pass; if one of them fails,
+/// its error is returned and the game is over, as `hj_game_phase` describes.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_pass(game: *mut HJGame, seat: usize, cards: *const HJCard, len: usize) -> HJStatus {
+    guard(|| {
+        let game = game_mut(game)?;
+        let cards = match len {
+            0 => vec![],
+            _ if cards.is_null() => return Err(null("cards")),
+            _ => std::slice::from_raw_parts(cards, len).iter().map(|c| card_from_c(*c)).collect::<Result<Vec<Card>, Failure>>()?,
+        };
+        Ok(game.0.pass(check_seat(seat)?, cards)?)
+    })
+}
+
+/// Plays a card for the current seat, which must not have a bot. Illegal cards are rejected and leave the game unchanged.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_play(game: *mut HJGame, card: HJCard) -> HJStatus {
+    guard(|| {
+        let game = game_mut(game)?;
+        game.0.play(card_from_c(card)?)?;
+        Ok(())
+    })
+}
+
+/// Lets the bot in the current seat play a card, and writes its move to `out_move` if that is not null. If the bot
+/// fails, its error is returned and the game is over, as `hj_game_phase` describes.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_advance(game: *mut HJGame, out_move: *mut HJMove) -> HJStatus {
+    guard(|| {
+        let new_move = game_mut(game)?.0.advance()?;
+        if let Some(out_move) = out_move.as_mut() { *out_move = move_to_c(&new_move); }
+        Ok(())
+    })
+}
+
+/// Writes the four scores: the points taken so far, or the final result once the game is finished.
+#[no_mangle]
+pub unsafe extern "C" fn hj_game_scores(game: *const HJGame, out_scores: *mut i64) -> HJStatus {
+    guard(|| {
+        let game_info = game_ref(game)?.0.game_info();
+        if out_scores.is_null() { return Err(null("out_scores")); }
+        let scores = game_info.result().unwrap_or_else(|| game_info.score()).map(|s| s as i64);
+        ptr::copy_nonoverlapping(scores.as_ptr(), out_scores, 4);
+        Ok(())
+    })
+}
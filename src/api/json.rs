@@ -1,26 +1,195 @@
+use crate::api::connection::BotConnection;
+use crate::api::supervisor::BotOptions;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::api::supervisor::BotProcess;
 use crate::game::actor::Actor;
-use crate::game::data::{Card, Move};
+use crate::game::data::{Card, Hand, Move};
+use crate::game::errors::HJError;
+use crate::game::game_info::GameInfo;
+use crate::rust_actors::player_state::{BasicPlayerStateInterface, DefaultPlayerStateInterface};
+use crate::rust_actors::util::get_allowed_cards;
 
-use std::process::{Child, Command, Stdio};
-use std::io::{Read, Write};
 
 use json;
 use num;
 use crate::PassDirection;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
 
-pub struct JSONActor {
-    child: Child,
+// The protocol versions this side speaks. Version 0 is the protocol from before the hello handshake.
+pub const MIN_PROTOCOL_VERSION: usize = 0;
+pub const PROTOCOL_VERSION: usize = 1;
+pub const RULE_VARIANT: &str = "omnibus";
+// With `state`, every message also carries the bot's view of the game, see `JSONActor::state`.
+pub const FEATURES: [&str; 1] = ["state"];
+
+#[derive(Clone, Debug)]
+pub struct BotInfo {
+    name: String,
+    author: String,
+    protocol_version: usize,
+    features: Vec<String>,
 }
 
-impl JSONActor {
+impl BotInfo {
+    // A bot from before the handshake, which speaks version 0 and has no features.
+    fn legacy(name: String) -> Self {
+        Self { name, author: "unknown".to_owned(), protocol_version: 0, features: vec![] }
+    }
+
+    // A reply without a protocol version comes from a bot that does not know the hello message.
+    fn from_json(value: &json::JsonValue, name: String) -> Result<Self, HJError> {
+        let invalid = |reason: &str| HJError::ProtocolError(format!("{} in hello reply {}", reason, value.dump()));
+
+        let Some(max_version) = value["protocol_version"].as_usize() else { return Ok(Self::legacy(name)) };
+        let min_version = value["min_protocol_version"].as_usize().unwrap_or(max_version);
+        // The highest version both sides speak.
+        let protocol_version = max_version.min(PROTOCOL_VERSION);
+        if protocol_version < min_version || !(MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&protocol_version) {
+            return Err(HJError::ProtocolError(format!(
+                "Bot speaks protocol versions {} to {}, but versions {} to {} are supported.",
+                min_version, max_version, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION,
+            )));
+        }
+
+        // Features this side does not know are left off; the bot sees which ones were offered in the hello message.
+        let features = value["features"].members()
+            .map(|f| f.as_str().map(|f| f.to_owned()).ok_or_else(|| invalid("Invalid feature")))
+            .collect::<Result<Vec<String>, HJError>>()?
+            .into_iter()
+            .filter(|f| FEATURES.contains(&f.as_str()))
+            .collect();
+
+        Ok(Self {
+            name: value["name"].as_str().ok_or_else(|| invalid("Missing name"))?.to_owned(),
+            author: value["author"].as_str().unwrap_or("unknown").to_owned(),
+            protocol_version,
+            features,
+        })
+    }
+
+    pub fn name(&self) -> &str { &self.name }
+
     #[allow(dead_code)]
-    pub fn new(command_string: String) -> Self {
-        Self {
-            child: Command::new(command_string).stdout(Stdio::piped())
-                                               .stdin(Stdio::piped())
-                                               .spawn()
-                                               .expect("Failed to start process.")
+    pub fn protocol_version(&self) -> usize { self.protocol_version }
+
+    pub fn author(&self) -> &str { &self.author }
+
+    pub fn has_feature(&self, feature: &str) -> bool { self.features.iter().any(|f| f == feature) }
+}
+
+pub struct JSONActor<C: BotConnection> {
+    connection: C,
+    options: BotOptions,
+    failed: bool,
+    info: Option<BotInfo>,
+
+    player_state: DefaultPlayerState,
+    round: usize,
+    pass_direction: PassDirection,
+    passed_cards: Vec<Card>,
+    received_cards: Vec<Card>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl JSONActor<BotProcess> {
+    #[allow(dead_code)]
+    pub fn new(command_string: String) -> Result<Self, HJError> {
+        Self::with_options(command_string, BotOptions::default())
+    }
+
+    pub fn with_options(command_string: String, options: BotOptions) -> Result<Self, HJError> {
+        let process = BotProcess::spawn(&command_string, &options)?;
+        JSONActor::connect(process, &options)
+    }
+}
+
+impl<C: BotConnection> JSONActor<C> {
+    pub fn connect(connection: C, options: &BotOptions) -> Result<Self, HJError> {
+        let mut actor = Self {
+            connection,
+            options: options.clone(),
+            failed: false,
+            info: None,
+            player_state: Default::default(),
+            round: 0,
+            pass_direction: PassDirection::None,
+            passed_cards: vec![],
+            received_cards: vec![],
+        };
+        actor.hello()?;
+        Ok(actor)
+    }
+
+    #[allow(dead_code)]
+    pub fn info(&self) -> &BotInfo { self.info.as_ref().expect("The handshake should have completed.") }
+
+    fn restart(&mut self) -> Result<(), HJError> {
+        self.options.event(&format!("Restarting bot {}.", self.connection.describe()));
+        self.connection.reconnect()?;
+        self.failed = false;
+        self.hello()
+    }
+
+    fn hello(&mut self) -> Result<(), HJError> {
+        let reply = self.request(json::object!{
+            message: "hello",
+            protocol_version: PROTOCOL_VERSION,
+            min_protocol_version: MIN_PROTOCOL_VERSION,
+            variant: RULE_VARIANT,
+            features: FEATURES.to_vec(),
+        });
+
+        let info = match reply {
+            Ok(reply) => BotInfo::from_json(&reply, self.connection.describe())?,
+            // An older bot may crash on or ignore the hello message; it is started afresh and spoken to without one.
+            Err(_) => {
+                self.connection.reconnect()?;
+                self.failed = false;
+                BotInfo::legacy(self.connection.describe())
+            },
+        };
+        self.options.event(&format!("Connected to bot {} by {}.", info.name(), info.author()));
+        self.info = Some(info);
+        Ok(())
+    }
+
+    fn state(&self, played_moves: Option<&Vec<Move>>) -> json::JsonValue {
+        let pidx = self.player_state.pidx();
+        let shift = self.pass_direction.index_shift();
+
+        let mut state = json::object!{
+            pidx: pidx,
+            hand: Self::serialize_cards(self.player_state.cards()),
+            trick: self.round,
+            hearts_broken: self.player_state.hearts_played(),
+            scores: self.player_state.scores().to_vec(),
+            pass: json::object!{
+                direction: self.pass_direction.name(),
+                to_pidx: (pidx + shift) % 4,
+                from_pidx: (pidx + 4 - shift) % 4,
+                passed_cards: Self::serialize_cards(&self.passed_cards),
+                received_cards: Self::serialize_cards(&self.received_cards),
+            },
+        };
+
+        if let Some(played_moves) = played_moves {
+            let mut hands = [Hand::new(vec![]), Hand::new(vec![]), Hand::new(vec![]), Hand::new(vec![])];
+            hands[pidx] = Hand::new(self.player_state.cards().clone());
+            let game_info = GameInfo::reconstruct(
+                played_moves.clone(), pidx, self.round, hands,
+                self.player_state.hearts_played(), *self.player_state.scores(), *self.player_state.scored(),
+            );
+            state["legal_cards"] = Self::serialize_cards(&get_allowed_cards(&game_info)).into();
         }
+
+        state
+    }
+
+    fn with_state(&self, mut message: json::JsonValue, played_moves: Option<&Vec<Move>>) -> json::JsonValue {
+        if self.info.as_ref().is_some_and(|info| info.has_feature("state")) {
+            message["state"] = self.state(played_moves);
+        }
+        message
     }
 
     fn serialize_cards(cards: &Vec<Card>) -> Vec<Vec<usize>> {
@@ -31,116 +200,315 @@ impl JSONActor {
         moves.iter().map(|m| vec![m.pidx(), m.card().rank() as usize, m.card().suit() as usize]).collect::<Vec<Vec<usize>>>()
     }
 
-    fn deserialize_card(card: Vec<usize>) -> Card {
-        Card::new(num::FromPrimitive::from_u8(card[0] as u8).unwrap(), num::FromPrimitive::from_u8(card[1] as u8).unwrap())
+    fn deserialize_card(card: &json::JsonValue) -> Result<Card, HJError> {
+        let invalid = || HJError::ProtocolError(format!("Expected a card as [rank, suit], got {}.", card.dump()));
+        if card.len() != 2 { return Err(invalid()); }
+
+        Ok(Card::new(
+            card[0].as_u8().and_then(num::FromPrimitive::from_u8).ok_or_else(invalid)?,
+            card[1].as_u8().and_then(num::FromPrimitive::from_u8).ok_or_else(invalid)?,
+        ))
+    }
+
+    fn deserialize_cards(cards: &json::JsonValue) -> Result<Vec<Card>, HJError> {
+        if !cards.is_array() {
+            return Err(HJError::ProtocolError(format!("Expected an array of cards, got {}.", cards.dump())));
+        }
+        cards.members().map(Self::deserialize_card).collect()
+    }
+
+    fn request(&mut self, message: json::JsonValue) -> Result<json::JsonValue, HJError> {
+        let timeout = self.options.timeout;
+        let reply = self.connection.send(&json::stringify(message)).and_then(|_| self.connection.receive(timeout));
+        let parsed = reply.and_then(|line| {
+            json::parse(&line).map_err(|error| HJError::ProtocolError(format!("Malformed JSON from bot ({}): {}", error, line.trim_end())))
+        });
+
+        // A bot that failed once is not trusted again; it is stopped now and restarted before the next game.
+        if parsed.is_err() {
+            self.failed = true;
+            self.connection.shutdown();
+        }
+        parsed
     }
 }
 
-impl Actor for JSONActor {
-    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) {
-        let message = json::object!{
+impl<C: BotConnection> Actor for JSONActor<C> {
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError> {
+        let restarted = self.failed || !self.connection.is_alive();
+        if restarted { self.restart()?; }
+
+        self.player_state = Default::default();
+        self.player_state.set_pidx(pidx);
+        self.player_state.set_cards(cards.clone());
+        self.round = 0;
+        self.pass_direction = PassDirection::None;
+        self.passed_cards = vec![];
+        self.received_cards = vec![];
+
+        let message = self.with_state(json::object!{
             message: "initialize",
             pidx: pidx,
-            cards: JSONActor::serialize_cards(cards),
-        };
-
-        (&mut self.child.stdin).as_mut().unwrap().write((json::stringify(message) + "\n").as_bytes()).unwrap();
-        let mut buffer = [32; 1];
-        let mut string = "".to_owned();
-        loop {
-            (&mut self.child.stdout.as_mut().unwrap()).take(1).read(&mut buffer).unwrap();
-            if buffer == [b'\n'] { break; }
-            else { string += &String::from_utf8_lossy(&buffer); }
+            cards: Self::serialize_cards(cards),
+        }, None);
+        // A bot may exit after each game to be started again, and may still be exiting when this is sent.
+        if self.request(message.clone()).is_err() && !restarted {
+            self.restart()?;
+            self.request(message)?;
         }
 
-        let _parsed = json::parse(&string).unwrap();
+        Ok(())
     }
 
-    fn play_card(&mut self, played_moves: &Vec<Move>) -> Card {
-        let message = json::object!{
-            message: "play_card",
-            played_moves: JSONActor::serialize_moves(played_moves),
-        };
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
+        self.player_state.update_play_card(played_moves);
 
-        (&mut self.child.stdin).as_mut().unwrap().write((json::stringify(message) + "\n").as_bytes()).unwrap();
-        let mut buffer = [32; 1];
-        let mut string = "".to_owned();
-        loop {
-            (&mut self.child.stdout.as_mut().unwrap()).take(1).read(&mut buffer).unwrap();
-            if buffer == [b'\n'] { break; }
-            else { string += &String::from_utf8_lossy(&buffer); }
-        }
-        let parsed = json::parse(&string).unwrap();
+        let message = self.with_state(json::object!{
+            message: "play_card",
+            played_moves: Self::serialize_moves(played_moves),
+        }, Some(played_moves));
+        let parsed = self.request(message)?;
 
-        match &parsed["card"] {
-            json::JsonValue::Array(vec) => JSONActor::deserialize_card(vec.iter().map(|num| num.as_usize().unwrap()).collect()),
-            _ => panic!("Should be an array."),
-        }
+        let card = Self::deserialize_card(&parsed["card"])?;
+        self.player_state.update_did_play_card(&card);
+        Ok(card)
     }
 
-    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) {
-        let message = json::object!{
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
+        self.player_state.update_end_round(played_moves, winner_pidx);
+        self.round += 1;
+
+        let message = self.with_state(json::object!{
             message: "end_round",
             winner_pidx: winner_pidx,
-            played_moves: JSONActor::serialize_moves(played_moves),
-        };
+            played_moves: Self::serialize_moves(played_moves),
+        }, None);
+        self.request(message)?;
 
-        (&mut self.child.stdin).as_mut().unwrap().write((json::stringify(message) + "\n").as_bytes()).unwrap();
-        let mut buffer = [32; 1000];
-        (&mut self.child.stdout.as_mut().unwrap()).read(&mut buffer).unwrap();
-        let _parsed = json::parse(&String::from_utf8_lossy(&buffer)).unwrap();
+        Ok(())
     }
 
-    fn end_game(&mut self, scores: [isize; 4]) {
-        let message = json::object!{
+    fn end_game(&mut self, scores: [isize; 4]) -> Result<(), HJError> {
+        let message = self.with_state(json::object!{
             message: "end_game",
             scores: scores.to_vec(),
-        };
+        }, None);
+        self.request(message)?;
 
-        (&mut self.child.stdin).as_mut().unwrap().write((json::stringify(message) + "\n").as_bytes()).unwrap();
-        let mut buffer = [32; 1000];
-        (&mut self.child.stdout.as_mut().unwrap()).read(&mut buffer).unwrap();
-        let _parsed = json::parse(&String::from_utf8_lossy(&buffer)).unwrap();
+        Ok(())
     }
 
-    fn get_pass(&mut self, direction: PassDirection) -> Vec<Card> {
-        let message = json::object!{
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError> {
+        self.pass_direction = direction;
+
+        let message = self.with_state(json::object!{
             message: "get_pass",
-            direction: match direction {
-                PassDirection::None => "none", PassDirection::Left => "left", PassDirection::Right => "right", PassDirection::Cross => "cross",
-            },
-        };
+            direction: direction.name(),
+        }, None);
+        let parsed = self.request(message)?;
+
+        let cards = Self::deserialize_cards(&parsed["cards"])?;
+        self.player_state.update_did_pass(&cards, direction);
+        self.passed_cards = cards.clone();
+        Ok(cards)
+    }
+
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError> {
+        self.player_state.update_end_pass(passed_cards);
+        self.received_cards = passed_cards.clone();
+
+        let message = self.with_state(json::object!{
+            message: "end_pass",
+            passed_cards: Self::serialize_cards(passed_cards),
+        }, None);
+        self.request(message)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::time::Duration;
 
-        (&mut self.child.stdin).as_mut().unwrap().write((json::stringify(message) + "\n").as_bytes()).unwrap();
-        let mut buffer = [32; 1];
-        let mut string = "".to_owned();
-        loop {
-            (&mut self.child.stdout.as_mut().unwrap()).take(1).read(&mut buffer).unwrap();
-            if buffer == [b'\n'] { break; }
-            else { string += &String::from_utf8_lossy(&buffer); }
+    use super::*;
+    use crate::util::deck::get_seeded_hands;
+
+    // Answers every line sent with the next scripted reply; a reply may take longer than the actor waits.
+    struct ScriptedBot {
+        replies: VecDeque<(Duration, String)>,
+        sent: Vec<String>,
+        alive: bool,
+        reconnects: usize,
+        can_reconnect: bool,
+    }
+
+    impl ScriptedBot {
+        fn new(replies: &[&str]) -> Self {
+            let mut bot = Self { replies: VecDeque::new(), sent: vec![], alive: true, reconnects: 0, can_reconnect: true };
+            for reply in replies { bot.reply(reply); }
+            bot
         }
-        let parsed = json::parse(&string).unwrap();
-
-        match &parsed["cards"] {
-            json::JsonValue::Array(vec) => vec.iter().map(|c|
-                match c {
-                    json::JsonValue::Array(vec) => JSONActor::deserialize_card(vec.iter().map(|num| num.as_usize().unwrap()).collect()),
-                    _ => panic!("Should be an array of arrays."),
-                }
-            ).collect(),
-            _ => panic!("Should be an array."),
+
+        fn reply(&mut self, line: &str) { self.replies.push_back((Duration::ZERO, line.to_owned())); }
+
+        fn reply_after(&mut self, delay: Duration, line: &str) { self.replies.push_back((delay, line.to_owned())); }
+    }
+
+    impl BotConnection for ScriptedBot {
+        fn describe(&self) -> String { "scripted".to_owned() }
+
+        fn send(&mut self, line: &str) -> Result<(), HJError> {
+            if !self.alive { return Err(HJError::ActorError("Cannot write to bot: closed".to_owned())); }
+            self.sent.push(line.to_owned());
+            Ok(())
         }
+
+        fn receive(&mut self, timeout: Duration) -> Result<String, HJError> {
+            match self.replies.pop_front() {
+                Some((delay, _)) if delay > timeout => Err(HJError::ActorError(format!("Bot did not reply within {} ms.", timeout.as_millis()))),
+                Some((_, line)) => Ok(line),
+                None => {
+                    self.alive = false;
+                    Err(HJError::ActorError("Bot closed its output.".to_owned()))
+                },
+            }
+        }
+
+        fn is_alive(&mut self) -> bool { self.alive }
+
+        fn reconnect(&mut self) -> Result<(), HJError> {
+            if !self.can_reconnect { return Err(HJError::ActorError("Cannot start bot.".to_owned())); }
+            self.reconnects += 1;
+            self.alive = true;
+            Ok(())
+        }
+
+        fn shutdown(&mut self) { self.alive = false; }
     }
 
-    fn end_pass(&mut self, passed_cards: &Vec<Card>) {
-        let message = json::object!{
-            message: "end_pass",
-            passed_cards: JSONActor::serialize_cards(passed_cards),
+    const HELLO: &str = r#"{"name": "scripted", "author": "tests", "protocol_version": 1}"#;
+
+    fn options() -> BotOptions {
+        BotOptions { timeout: Duration::from_millis(100), ..Default::default() }
+    }
+
+    // A bot that has said hello and been dealt seat 0's cards of the first seeded deal.
+    fn dealt(replies: &[&str]) -> (JSONActor<ScriptedBot>, Vec<Card>) {
+        let cards = get_seeded_hands(0)[0].cards().clone();
+        let mut actor = JSONActor::connect(ScriptedBot::new(&[&[HELLO, "{}"], replies].concat()), &options()).unwrap();
+        actor.initialize(0, &cards).unwrap();
+        (actor, cards)
+    }
+
+    fn sent_message(actor: &JSONActor<ScriptedBot>, index: usize) -> json::JsonValue {
+        json::parse(&actor.connection.sent[index]).unwrap()
+    }
+
+    #[test]
+    fn hello_negotiates_version_and_features() {
+        let reply = r#"{"name": "bot", "author": "me", "protocol_version": 3, "min_protocol_version": 0, "features": ["state", "telepathy"]}"#;
+        let actor = JSONActor::connect(ScriptedBot::new(&[reply]), &options()).unwrap();
+
+        let hello = sent_message(&actor, 0);
+        assert_eq!(hello["message"], "hello");
+        assert_eq!(hello["protocol_version"], PROTOCOL_VERSION);
+        let info = actor.info();
+        assert_eq!((info.name(), info.author(), info.protocol_version()), ("bot", "me", PROTOCOL_VERSION));
+        assert!(info.has_feature("state") && !info.has_feature("telepathy"), "{:?}", info);
+    }
+
+    #[test]
+    fn hello_rejects_unsupported_versions() {
+        for reply in [
+            r#"{"name": "bot", "protocol_version": 3, "min_protocol_version": 2}"#,
+            r#"{"protocol_version": 1}"#,
+            r#"{"name": "bot", "protocol_version": 1, "features": [1]}"#,
+        ] {
+            let result = JSONActor::connect(ScriptedBot::new(&[reply]), &options());
+            assert!(matches!(result, Err(HJError::ProtocolError(_))), "{}", reply);
+        }
+    }
+
+    #[test]
+    fn bots_without_hello_are_legacy() {
+        // One that answers without a version, and one that fails on the hello and is started again.
+        let actor = JSONActor::connect(ScriptedBot::new(&["{}"]), &options()).unwrap();
+        assert_eq!(actor.info().protocol_version(), 0);
+        assert_eq!(actor.connection.reconnects, 0);
+
+        let actor = JSONActor::connect(ScriptedBot::new(&["Unknown message hello"]), &options()).unwrap();
+        assert_eq!((actor.info().name(), actor.info().protocol_version()), ("scripted", 0));
+        assert_eq!(actor.connection.reconnects, 1);
+        assert!(!actor.failed && actor.connection.alive);
+
+        let mut bot = ScriptedBot::new(&[]);
+        bot.can_reconnect = false;
+        assert!(matches!(JSONActor::connect(bot, &options()), Err(HJError::ActorError(_))));
+    }
+
+    #[test]
+    fn state_is_sent_only_when_agreed() {
+        let (actor, _) = dealt(&[]);
+        assert!(sent_message(&actor, 1)["state"].is_null());
+
+        let cards = get_seeded_hands(0)[0].cards().clone();
+        let hello = r#"{"name": "bot", "protocol_version": 1, "features": ["state"]}"#;
+        let mut actor = JSONActor::connect(ScriptedBot::new(&[hello, "{}"]), &options()).unwrap();
+        actor.initialize(0, &cards).unwrap();
+        assert_eq!(sent_message(&actor, 1)["state"]["hand"].len(), 13);
+    }
+
+    #[test]
+    fn long_replies_are_read_whole() {
+        let (mut actor, cards) = dealt(&[]);
+        let reply = json::object!{
+            cards: JSONActor::<ScriptedBot>::serialize_cards(&cards[..3].to_vec()),
+            comment: "x".repeat(5000),
         };
+        actor.connection.reply(&json::stringify(reply));
+
+        assert_eq!(actor.get_pass(PassDirection::Left).unwrap(), cards[..3].to_vec());
+        assert!(!actor.failed);
+    }
+
+    #[test]
+    fn malformed_replies_are_protocol_errors() {
+        for reply in ["{\"card\": [0, ", "{\"card\": [13, 0]}", "{\"card\": \"QS\"}", "{}"] {
+            let (mut actor, _) = dealt(&[reply]);
+            assert!(matches!(actor.play_card(&vec![]), Err(HJError::ProtocolError(_))), "{}", reply);
+        }
+
+        // Only a reply that is not JSON at all stops the bot.
+        let (mut actor, _) = dealt(&["not json"]);
+        assert!(matches!(actor.end_pass(&vec![]), Err(HJError::ProtocolError(_))));
+        assert!(actor.failed && !actor.connection.alive);
+    }
+
+    #[test]
+    fn bot_going_away_mid_game_is_an_actor_error() {
+        let (mut actor, cards) = dealt(&[]);
+        assert!(matches!(actor.end_round(0, &vec![]), Err(HJError::ActorError(_))));
+        assert!(actor.failed);
+
+        // The bot is started again and greeted before the next game.
+        actor.connection.reply(HELLO);
+        actor.connection.reply("{}");
+        actor.initialize(1, &cards).unwrap();
+        assert_eq!(actor.connection.reconnects, 1);
+        assert_eq!(sent_message(&actor, 3)["message"], "hello");
+        assert_eq!(sent_message(&actor, 4)["pidx"], 1);
+    }
+
+    #[test]
+    fn slow_replies_time_out() {
+        let (mut actor, _) = dealt(&[]);
+        actor.connection.reply_after(Duration::from_millis(50), "{}");
+        actor.end_game([0, 0, 0, 26]).unwrap();
 
-        (&mut self.child.stdin).as_mut().unwrap().write((json::stringify(message) + "\n").as_bytes()).unwrap();
-        let mut buffer = [32; 1000];
-        (&mut self.child.stdout.as_mut().unwrap()).read(&mut buffer).unwrap();
-        let _parsed = json::parse(&String::from_utf8_lossy(&buffer)).unwrap();
+        actor.connection.reply_after(Duration::from_millis(500), "{}");
+        assert!(matches!(actor.end_game([0, 0, 0, 26]), Err(HJError::ActorError(_))));
+        assert!(actor.failed && !actor.connection.alive);
     }
 }
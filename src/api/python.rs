@@ -0,0 +1,275 @@
+// The pyo3 macros expand `?` on `PyResult` in a way clippy misreads.
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::api::rust::play_match;
+use crate::api::supervisor::BotOptions;
+use crate::game::actor::Actor;
+use crate::game::data::{Card, Hand, Move, PassDirection};
+use crate::game::errors::HJError;
+use crate::game::game_info::GameInfo;
+use crate::internal::actor_spec::create_actor;
+use crate::internal::hj_validator::HJValidator;
+use crate::internal::record::GameRecord;
+use crate::rust_actors::util::get_allowed_cards;
+use crate::util::deals::{self, Deal};
+use crate::util::deck::get_seeded_hands;
+
+// Cards cross the boundary as strings like `QS` and `10H`, moves as `(pidx, card)` tuples.
+
+impl From<HJError> for PyErr {
+    fn from(error: HJError) -> Self {
+        PyValueError::new_err(format!("{:?}", error))
+    }
+}
+
+fn cards_to_py(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|c| c.to_string()).collect()
+}
+
+fn cards_from_py(cards: Vec<String>) -> Result<Vec<Card>, HJError> {
+    cards.iter().map(|c| c.parse()).collect()
+}
+
+fn moves_to_py(moves: &[Move]) -> Vec<(usize, String)> {
+    moves.iter().map(|m| (m.pidx(), m.card().to_string())).collect()
+}
+
+fn moves_from_py(moves: Vec<(usize, String)>) -> Result<Vec<Move>, HJError> {
+    moves.into_iter().map(|(pidx, card)| Ok(Move(pidx, card.parse()?))).collect()
+}
+
+fn hands_from_py(hands: Vec<Vec<String>>) -> Result<[Hand; 4], HJError> {
+    let hands = hands.into_iter().map(|h| cards_from_py(h).map(Hand::new)).collect::<Result<Vec<Hand>, HJError>>()?;
+    let hands: [Hand; 4] = hands.try_into().map_err(|_| HJError::InvalidDealError("Expected four hands.".to_owned()))?;
+    // `GameInfo` assumes a full deal and would panic the interpreter on anything else.
+    deals::check_hands(&hands).map_err(|reason| HJError::InvalidDealError(format!("{}.", reason)))?;
+    Ok(hands)
+}
+
+fn direction_from_py(direction: &str) -> Result<PassDirection, HJError> {
+    PassDirection::from_name(direction).ok_or_else(|| HJError::InvalidSpecError(format!("Unknown pass direction '{}'.", direction)))
+}
+
+#[pyclass(name = "Deal")]
+struct PyDeal(Deal);
+
+#[pymethods]
+impl PyDeal {
+    #[getter]
+    fn id(&self) -> String { self.0.id().to_owned() }
+
+    #[getter]
+    fn seed(&self) -> Option<u64> { self.0.seed() }
+
+    #[getter]
+    fn hands(&self) -> Vec<Vec<String>> { self.0.hands().iter().map(|h| cards_to_py(h.cards())).collect() }
+
+    #[getter]
+    fn pass_direction(&self) -> &'static str { self.0.pass_direction().name() }
+}
+
+#[pyfunction]
+fn generate_deals(count: usize, seed: u64) -> Vec<PyDeal> {
+    deals::generate_deals(count, seed).into_iter().map(PyDeal).collect()
+}
+
+#[pyfunction]
+fn load_deals(path: &str) -> PyResult<Vec<PyDeal>> {
+    Ok(deals::load_deals(path)?.into_iter().map(PyDeal).collect())
+}
+
+// A game that is stepped by hand, with every pass and card checked against the rules.
+#[pyclass(name = "Game")]
+#[derive(Clone)]
+struct PyGame(GameInfo);
+
+#[pymethods]
+impl PyGame {
+    #[new]
+    #[pyo3(signature = (hands=None, seed=None))]
+    fn new(hands: Option<Vec<Vec<String>>>, seed: Option<u64>) -> PyResult<Self> {
+        let hands = match (hands, seed) {
+            (Some(hands), None) => hands_from_py(hands)?,
+            (None, Some(seed)) => get_seeded_hands(seed),
+            (None, None) => get_seeded_hands(rand::random()),
+            (Some(_), Some(_)) => return Err(PyValueError::new_err("Pass either hands or a seed, not both.")),
+        };
+        if !hands.iter().any(|h| h.any_match(Card(crate::Rank::Two, crate::Suit::Clubs))) {
+            return Err(PyValueError::new_err("Someone should have the two of clubs."));
+        }
+
+        Ok(Self(GameInfo::new(hands)))
+    }
+
+    #[getter]
+    fn current_pidx(&self) -> usize { self.0.current_pidx() }
+
+    #[getter]
+    fn current_round(&self) -> usize { self.0.current_round() }
+
+    #[getter]
+    fn current_moves(&self) -> Vec<(usize, String)> { moves_to_py(self.0.current_moves()) }
+
+    #[getter]
+    fn hands(&self) -> Vec<Vec<String>> { self.0.hands().iter().map(|h| cards_to_py(h.cards())).collect() }
+
+    #[getter]
+    fn hearts_played(&self) -> bool { self.0.hearts_played() }
+
+    #[getter]
+    fn score(&self) -> [isize; 4] { self.0.score() }
+
+    #[getter]
+    fn result(&self) -> Option<[isize; 4]> { self.0.result() }
+
+    fn legal_cards(&self) -> Vec<String> { cards_to_py(&get_allowed_cards(&self.0)) }
+
+    fn pass_cards(&mut self, direction: &str, passed_cards: Vec<Vec<String>>) -> PyResult<()> {
+        let passed_cards = passed_cards.into_iter().map(cards_from_py).collect::<Result<Vec<Vec<Card>>, HJError>>()?;
+        let passed_cards: [Vec<Card>; 4] = passed_cards.try_into()
+            .map_err(|_| PyValueError::new_err("Expected passed cards for four players."))?;
+        Ok(self.0.apply_pass::<HJValidator>(direction_from_py(direction)?, &passed_cards)?)
+    }
+
+    fn play(&mut self, card: &str) -> PyResult<()> {
+        Ok(self.0.apply_move::<HJValidator>(card.parse()?)?)
+    }
+
+    fn copy(&self) -> Self { self.clone() }
+}
+
+// One of the built-in actors, e.g. `Actor("rule_v1")` or `Actor("mcts_v1:50:10")`.
+#[pyclass(name = "Actor", unsendable)]
+struct PyBuiltinActor(Box<dyn Actor>);
+
+#[pymethods]
+impl PyBuiltinActor {
+    #[new]
+    fn new(spec: &str) -> PyResult<Self> {
+        Ok(Self(create_actor(spec, &BotOptions::default())?))
+    }
+
+    fn initialize(&mut self, pidx: usize, cards: Vec<String>) -> PyResult<()> {
+        Ok(self.0.initialize(pidx, &cards_from_py(cards)?)?)
+    }
+
+    fn play_card(&mut self, played_moves: Vec<(usize, String)>) -> PyResult<String> {
+        Ok(self.0.play_card(&moves_from_py(played_moves)?)?.to_string())
+    }
+
+    fn end_round(&mut self, winner_pidx: usize, played_moves: Vec<(usize, String)>) -> PyResult<()> {
+        Ok(self.0.end_round(winner_pidx, &moves_from_py(played_moves)?)?)
+    }
+
+    fn end_game(&mut self, scores: [isize; 4]) -> PyResult<()> {
+        Ok(self.0.end_game(scores)?)
+    }
+
+    fn get_pass(&mut self, direction: &str) -> PyResult<Vec<String>> {
+        Ok(cards_to_py(&self.0.get_pass(direction_from_py(direction)?)?))
+    }
+
+    fn end_pass(&mut self, passed_cards: Vec<String>) -> PyResult<()> {
+        Ok(self.0.end_pass(&cards_from_py(passed_cards)?)?)
+    }
+}
+
+// Lets any Python object with the `Actor` methods take a seat, including the built-in `Actor` class.
+struct PyActor(PyObject);
+
+impl PyActor {
+    fn call<'py, A: IntoPy<Py<pyo3::types::PyTuple>>>(&self, py: Python<'py>, method: &str, args: A) -> Result<Bound<'py, PyAny>, HJError> {
+        self.0.bind(py).call_method1(method, args)
+            .map_err(|e| HJError::ActorError(format!("Python actor failed in {}: {}", method, e)))
+    }
+
+    fn extract<'py, T: FromPyObject<'py>>(method: &str, value: Bound<'py, PyAny>) -> Result<T, HJError> {
+        value.extract().map_err(|e| HJError::ActorError(format!("Python actor returned an invalid value from {}: {}", method, e)))
+    }
+}
+
+impl Actor for PyActor {
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError> {
+        Python::with_gil(|py| self.call(py, "initialize", (pidx, cards_to_py(cards))).map(|_| ()))
+    }
+
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
+        Python::with_gil(|py| {
+            let card: String = Self::extract("play_card", self.call(py, "play_card", (moves_to_py(played_moves),))?)?;
+            card.parse()
+        })
+    }
+
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
+        Python::with_gil(|py| self.call(py, "end_round", (winner_pidx, moves_to_py(played_moves))).map(|_| ()))
+    }
+
+    fn end_game(&mut self, scores: [isize; 4]) -> Result<(), HJError> {
+        Python::with_gil(|py| self.call(py, "end_game", (scores,)).map(|_| ()))
+    }
+
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError> {
+        Python::with_gil(|py| {
+            let cards: Vec<String> = Self::extract("get_pass", self.call(py, "get_pass", (direction.name(),))?)?;
+            cards_from_py(cards)
+        })
+    }
+
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError> {
+        Python::with_gil(|py| self.call(py, "end_pass", (cards_to_py(passed_cards),)).map(|_| ()))
+    }
+}
+
+#[pyclass(name = "Record")]
+struct PyRecord(GameRecord);
+
+#[pymethods]
+impl PyRecord {
+    #[getter]
+    fn scores(&self) -> [isize; 4] { self.0.scores() }
+
+    #[getter]
+    fn hands(&self) -> Vec<Vec<String>> { self.0.hands().iter().map(|h| cards_to_py(h.cards())).collect() }
+
+    #[getter]
+    fn pass_direction(&self) -> &'static str { self.0.pass_direction().name() }
+
+    #[getter]
+    fn passes(&self) -> Vec<Vec<String>> { self.0.passes().iter().map(|p| cards_to_py(p)).collect() }
+
+    // Every trick as `(winner_pidx, moves)`.
+    #[getter]
+    fn tricks(&self) -> Vec<(usize, Vec<(usize, String)>)> {
+        self.0.tricks().iter().map(|t| (t.winner_pidx(), moves_to_py(t.moves()))).collect()
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (actors, hands=None, seed=None, pass_direction="none"))]
+fn play(actors: Vec<PyObject>, hands: Option<Vec<Vec<String>>>, seed: Option<u64>, pass_direction: &str) -> PyResult<PyRecord> {
+    let actors: [PyObject; 4] = actors.try_into().map_err(|_| PyValueError::new_err("A match needs four actors."))?;
+    let hands = match (hands, seed) {
+        (Some(hands), None) => hands_from_py(hands)?,
+        (None, Some(seed)) => get_seeded_hands(seed),
+        (None, None) => get_seeded_hands(rand::random()),
+        (Some(_), Some(_)) => return Err(PyValueError::new_err("Pass either hands or a seed, not both.")),
+    };
+
+    let actors = actors.map(|a| Box::new(PyActor(a)) as Box<dyn Actor>);
+    Ok(PyRecord(play_match(hands, direction_from_py(pass_direction)?, actors)?))
+}
+
+#[pymodule]
+fn hj(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyDeal>()?;
+    m.add_class::<PyGame>()?;
+    m.add_class::<PyBuiltinActor>()?;
+    m.add_class::<PyRecord>()?;
+    m.add_function(wrap_pyfunction!(generate_deals, m)?)?;
+    m.add_function(wrap_pyfunction!(load_deals, m)?)?;
+    m.add_function(wrap_pyfunction!(play, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::game::actor::Actor;
+use crate::game::data::{Hand, PassDirection};
+use crate::game::errors::HJError;
+use crate::internal::game::play_recorded;
+use crate::internal::record::GameRecord;
+use crate::rust_actors::actor_ismcts_v1::{ActorISMCTSV1, Observers};
+use crate::rust_actors::actor_mcts_mod_v1::ActorMCTSModV1;
+use crate::rust_actors::actor_mcts_v1::ActorMCTSV1;
+use crate::rust_actors::actor_random::ActorRandom;
+use crate::util::deals::Deal;
+use crate::util::deck::get_seeded_hands;
+use crate::rust_actors::actor_rule_v1::ActorRuleV1;
+use crate::rust_actors::player_state::basic_player_state::BasicPlayerState;
+use crate::rust_actors::determinize::determinize_v1::DeterminizeV1;
+use crate::rust_actors::eval_round::eval_round_v1::EvalRoundV1;
+use crate::rust_actors::eval_state::eval_state_v1::EvalStateV1;
+use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
+
+// The built-in actors with their usual components filled in.
+pub type RandomActor = ActorRandom<BasicPlayerState>;
+pub type RuleV1 = ActorRuleV1<EvalRoundV1, EvalStateV1, ExtendedPlayerState>;
+pub type MCTSV1 = ActorMCTSV1<DeterminizeV1, EvalStateV1, RuleV1, ExtendedPlayerState>;
+pub type MCTSModV1 = ActorMCTSModV1<DeterminizeV1, EvalStateV1, RuleV1, ExtendedPlayerState>;
+pub type ISMCTSV1 = ActorISMCTSV1<DeterminizeV1, EvalStateV1, RuleV1, ExtendedPlayerState>;
+
+impl MCTSV1 {
+    pub fn builder() -> MCTSV1Builder { MCTSV1Builder::default() }
+}
+
+impl MCTSModV1 {
+    pub fn builder() -> MCTSModV1Builder { MCTSModV1Builder::default() }
+}
+
+impl ISMCTSV1 {
+    pub fn builder() -> ISMCTSV1Builder { ISMCTSV1Builder::default() }
+}
+
+#[derive(Clone, Debug)]
+pub struct MCTSV1Builder {
+    timeout: usize,
+    tries: usize,
+}
+
+impl Default for MCTSV1Builder {
+    fn default() -> Self { Self { timeout: 50, tries: 10 } }
+}
+
+impl MCTSV1Builder {
+    // Milliseconds spent searching each determinization.
+    pub fn timeout(self, timeout: usize) -> Self { Self { timeout, ..self } }
+
+    // Number of determinizations searched per move.
+    pub fn tries(self, tries: usize) -> Self { Self { tries, ..self } }
+
+    pub fn build(self) -> MCTSV1 { MCTSV1::new(self.timeout, self.tries) }
+}
+
+#[derive(Clone, Debug)]
+pub struct MCTSModV1Builder {
+    timeout: usize,
+}
+
+impl Default for MCTSModV1Builder {
+    fn default() -> Self { Self { timeout: 50 } }
+}
+
+impl MCTSModV1Builder {
+    // Milliseconds spent searching per move.
+    pub fn timeout(self, timeout: usize) -> Self { Self { timeout } }
+
+    pub fn build(self) -> MCTSModV1 { MCTSModV1::new(self.timeout) }
+}
+
+#[derive(Clone, Debug)]
+pub struct ISMCTSV1Builder {
+    timeout: usize,
+    observers: Observers,
+}
+
+impl Default for ISMCTSV1Builder {
+    fn default() -> Self { Self { timeout: 500, observers: Observers::Single } }
+}
+
+impl ISMCTSV1Builder {
+    // Milliseconds spent searching per move.
+    pub fn timeout(self, timeout: usize) -> Self { Self { timeout, ..self } }
+
+    // Whether every seat keeps its own tree instead of sharing the searching player's.
+    pub fn multi_observer(self, multi_observer: bool) -> Self {
+        Self { observers: if multi_observer { Observers::Multi } else { Observers::Single }, ..self }
+    }
+
+    pub fn build(self) -> ISMCTSV1 { ISMCTSV1::customize(self.timeout, self.observers) }
+}
+
+// Sets up a single game: four actors in seat order, and either explicit hands, a deal or a seed.
+#[derive(Default)]
+pub struct MatchBuilder {
+    actors: Vec<Box<dyn Actor>>,
+    hands: Option<[Hand; 4]>,
+    pass_direction: Option<PassDirection>,
+}
+
+impl MatchBuilder {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn actor(mut self, actor: impl Actor + 'static) -> Self {
+        self.actors.push(Box::new(actor));
+        self
+    }
+
+    pub fn boxed_actor(mut self, actor: Box<dyn Actor>) -> Self {
+        self.actors.push(actor);
+        self
+    }
+
+    pub fn hands(self, hands: [Hand; 4]) -> Self { Self { hands: Some(hands), ..self } }
+
+    pub fn seed(self, seed: u64) -> Self { self.hands(get_seeded_hands(seed)) }
+
+    pub fn deal(self, deal: &Deal) -> Self {
+        Self { hands: Some(deal.hands().clone()), pass_direction: Some(deal.pass_direction()), ..self }
+    }
+
+    pub fn pass_direction(self, pass_direction: PassDirection) -> Self { Self { pass_direction: Some(pass_direction), ..self } }
+
+    pub fn play(self) -> Result<GameRecord, HJError> {
+        let actors: [Box<dyn Actor>; 4] = self.actors.try_into()
+            .map_err(|actors: Vec<_>| HJError::InvalidSpecError(format!("A match needs four actors, got {}.", actors.len())))?;
+        let hands = self.hands.unwrap_or_else(|| get_seeded_hands(rand::Rng::gen(&mut StdRng::from_entropy())));
+
+        play_match(hands, self.pass_direction.unwrap_or(PassDirection::None), actors)
+    }
+}
+
+pub fn play_match(hands: [Hand; 4], pass_direction: PassDirection, actors: [Box<dyn Actor>; 4]) -> Result<GameRecord, HJError> {
+    let [mut a0, mut a1, mut a2, mut a3] = actors;
+    play_recorded(hands, pass_direction, [a0.as_mut(), a1.as_mut(), a2.as_mut(), a3.as_mut()])
+}
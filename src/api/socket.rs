@@ -0,0 +1,286 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::connection::BotConnection;
+use crate::api::json::JSONActor;
+use crate::api::supervisor::BotOptions;
+use crate::game::errors::HJError;
+use crate::util::platform::Instant;
+
+pub type SocketActor = JSONActor<BotSocket>;
+
+// Addresses look like `tcp:HOST:PORT` (or just `HOST:PORT`) and `unix:PATH`.
+pub enum SocketStream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl SocketStream {
+    pub fn connect(address: &str) -> io::Result<Self> {
+        match address.split_once(':') {
+            #[cfg(unix)]
+            Some(("unix", path)) => Ok(Self::Unix(UnixStream::connect(path)?)),
+            Some(("tcp", address)) => Ok(Self::Tcp(TcpStream::connect(address)?)),
+            _ => Ok(Self::Tcp(TcpStream::connect(address)?)),
+        }
+    }
+
+    fn try_clone(&self) -> io::Result<Self> {
+        match self {
+            Self::Tcp(stream) => Ok(Self::Tcp(stream.try_clone()?)),
+            #[cfg(unix)]
+            Self::Unix(stream) => Ok(Self::Unix(stream.try_clone()?)),
+        }
+    }
+
+    fn set_read_timeout(&self, timeout: Duration) -> io::Result<()> {
+        let timeout = Some(timeout.max(Duration::from_millis(1)));
+        match self {
+            Self::Tcp(stream) => stream.set_read_timeout(timeout),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.set_read_timeout(timeout),
+        }
+    }
+
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.set_nonblocking(nonblocking),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.set_nonblocking(nonblocking),
+        }
+    }
+
+    fn shutdown(&self) {
+        let _ = match self {
+            Self::Tcp(stream) => stream.shutdown(Shutdown::Both),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.shutdown(Shutdown::Both),
+        };
+    }
+}
+
+impl Read for SocketStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for SocketStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(stream) => stream.write(buf),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Tcp(stream) => stream.flush(),
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+// How a lost connection is replaced.
+enum Reopen {
+    // We connected to the bot, so we connect again.
+    Connect(String),
+    // The bot connected to us, so we wait this long for it, or another bot, to connect again.
+    Accept(Arc<SocketListener>, Duration),
+}
+
+pub struct BotSocket {
+    peer: String,
+    reopen: Reopen,
+    reader: BufReader<SocketStream>,
+    writer: SocketStream,
+    closed: bool,
+}
+
+impl BotSocket {
+    pub fn connect(address: &str) -> Result<Self, HJError> {
+        let stream = SocketStream::connect(address)
+            .map_err(|e| HJError::ActorError(format!("Cannot connect to bot at {}: {}", address, e)))?;
+        Self::from_stream(stream, address.to_owned(), Reopen::Connect(address.to_owned()))
+    }
+
+    fn from_stream(stream: SocketStream, peer: String, reopen: Reopen) -> Result<Self, HJError> {
+        let writer = stream.try_clone().map_err(|e| HJError::ActorError(format!("Cannot use connection to {}: {}", peer, e)))?;
+        Ok(Self { peer, reopen, reader: BufReader::new(stream), writer, closed: false })
+    }
+}
+
+impl BotConnection for BotSocket {
+    fn describe(&self) -> String { self.peer.clone() }
+
+    fn send(&mut self, line: &str) -> Result<(), HJError> {
+        let writer = &mut self.writer;
+        let result = writer.write_all(line.as_bytes()).and_then(|_| writer.write_all(b"\n")).and_then(|_| writer.flush());
+        if let Err(error) = result {
+            self.closed = true;
+            return Err(HJError::ActorError(format!("Cannot write to bot at {}: {}", self.peer, error)));
+        }
+
+        Ok(())
+    }
+
+    fn receive(&mut self, timeout: Duration) -> Result<String, HJError> {
+        let mut line = String::new();
+        self.reader.get_ref().set_read_timeout(timeout)
+            .map_err(|e| HJError::ActorError(format!("Cannot use connection to {}: {}", self.peer, e)))?;
+
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => {
+                    self.closed = true;
+                    return Err(HJError::ActorError(format!("Bot at {} closed the connection.", self.peer)));
+                },
+                Ok(_) if line.trim().is_empty() => continue,
+                Ok(_) => return Ok(line),
+                Err(error) if matches!(error.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+                    return Err(HJError::ActorError(format!("Bot at {} did not reply within {} ms.", self.peer, timeout.as_millis())));
+                },
+                Err(error) => return Err(HJError::ActorError(format!("Cannot read from bot at {}: {}", self.peer, error))),
+            }
+        }
+    }
+
+    fn is_alive(&mut self) -> bool { !self.closed }
+
+    fn reconnect(&mut self) -> Result<(), HJError> {
+        self.shutdown();
+        match &self.reopen {
+            Reopen::Connect(address) => { *self = Self::connect(&address.clone())?; },
+            Reopen::Accept(listener, timeout) => {
+                let (stream, peer) = listener.accept_within(*timeout)
+                    .map_err(|e| HJError::ActorError(format!("No bot connected again in place of {}: {}", self.peer, e)))?;
+                *self = Self::from_stream(stream, peer, Reopen::Accept(listener.clone(), *timeout))?;
+            },
+        }
+        Ok(())
+    }
+
+    fn shutdown(&mut self) {
+        self.writer.shutdown();
+        self.closed = true;
+    }
+}
+
+impl Drop for BotSocket {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+enum SocketListener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, String),
+}
+
+impl SocketListener {
+    fn accept(&self) -> io::Result<(SocketStream, String)> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, peer) = listener.accept()?;
+                Ok((SocketStream::Tcp(stream), format!("tcp:{}", peer)))
+            },
+            #[cfg(unix)]
+            Self::Unix(listener, path) => {
+                let (stream, _) = listener.accept()?;
+                Ok((SocketStream::Unix(stream), format!("unix:{}", path)))
+            },
+        }
+    }
+
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match self {
+            Self::Tcp(listener) => listener.set_nonblocking(nonblocking),
+            #[cfg(unix)]
+            Self::Unix(listener, _) => listener.set_nonblocking(nonblocking),
+        }
+    }
+
+    fn accept_within(&self, timeout: Duration) -> io::Result<(SocketStream, String)> {
+        let start = Instant::now();
+        self.set_nonblocking(true)?;
+        let accepted = loop {
+            match self.accept() {
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock && start.elapsed() < timeout => {
+                    std::thread::sleep(Duration::from_millis(10));
+                },
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break Err(io::ErrorKind::TimedOut.into()),
+                accepted => break accepted,
+            }
+        };
+        self.set_nonblocking(false)?;
+
+        let (stream, peer) = accepted?;
+        stream.set_nonblocking(false)?;
+        Ok((stream, peer))
+    }
+}
+
+// Waits for bots to connect, so they can run as separate long-lived processes.
+pub struct SocketServer {
+    listener: Arc<SocketListener>,
+    options: BotOptions,
+}
+
+impl SocketServer {
+    pub fn bind(address: &str, options: BotOptions) -> Result<Self, HJError> {
+        let bind_error = |e: io::Error| HJError::ActorError(format!("Cannot listen on {}: {}", address, e));
+        let listener = match address.split_once(':') {
+            #[cfg(unix)]
+            Some(("unix", path)) => SocketListener::Unix(UnixListener::bind(path).map_err(bind_error)?, path.to_owned()),
+            Some(("tcp", address)) => SocketListener::Tcp(TcpListener::bind(address).map_err(bind_error)?),
+            _ => SocketListener::Tcp(TcpListener::bind(address).map_err(bind_error)?),
+        };
+
+        Ok(Self { listener: Arc::new(listener), options })
+    }
+
+    pub fn local_address(&self) -> String {
+        match self.listener.as_ref() {
+            SocketListener::Tcp(listener) => listener.local_addr().map_or("tcp:?".to_owned(), |a| format!("tcp:{}", a)),
+            #[cfg(unix)]
+            SocketListener::Unix(_, path) => format!("unix:{}", path),
+        }
+    }
+
+    // A bot that drops its connection later is replaced by the next one to connect.
+    pub fn accept(&self) -> Result<SocketActor, HJError> {
+        let (stream, peer) = self.listener.accept().map_err(|e| HJError::ActorError(format!("Cannot accept a connection: {}", e)))?;
+        let reopen = Reopen::Accept(self.listener.clone(), self.options.timeout);
+        SocketActor::connect(BotSocket::from_stream(stream, peer, reopen)?, &self.options)
+    }
+
+    pub fn accept_seats(&self) -> Result<[SocketActor; 4], HJError> {
+        let mut seats = vec![];
+        for pidx in 0..4 {
+            seats.push(self.accept()?);
+            self.options.event(&format!("Seat {} taken.", pidx));
+        }
+
+        Ok(seats.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+}
+
+impl Drop for SocketServer {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let SocketListener::Unix(_, path) = self.listener.as_ref() { let _ = std::fs::remove_file(path); }
+    }
+}
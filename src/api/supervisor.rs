@@ -0,0 +1,42 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+// Bots run as child processes, which `wasm32-unknown-unknown` does not have.
+#[cfg(not(target_arch = "wasm32"))]
+mod process;
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::api::supervisor::process::BotProcess;
+
+// Told about things worth showing to whoever runs the bots, like a restart or a seat being taken.
+// The library prints nothing itself.
+pub type EventHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+#[derive(Clone)]
+pub struct BotOptions {
+    pub timeout: Duration,
+    pub log_dir: Option<String>,
+    pub on_event: Option<EventHandler>,
+}
+
+impl BotOptions {
+    pub(crate) fn event(&self, message: &str) {
+        if let Some(on_event) = &self.on_event { on_event(message); }
+    }
+}
+
+impl Default for BotOptions {
+    fn default() -> Self {
+        Self { timeout: Duration::from_secs(10), log_dir: None, on_event: None }
+    }
+}
+
+impl fmt::Debug for BotOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BotOptions")
+            .field("timeout", &self.timeout)
+            .field("log_dir", &self.log_dir)
+            .field("on_event", &self.on_event.is_some())
+            .finish()
+    }
+}
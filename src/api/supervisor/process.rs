@@ -0,0 +1,128 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::api::connection::BotConnection;
+use crate::api::supervisor::BotOptions;
+use crate::game::errors::HJError;
+
+// A running bot. Its output is read on a separate thread, so that replies can be awaited with a timeout.
+pub struct BotProcess {
+    command: String,
+    options: BotOptions,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<io::Result<String>>,
+}
+
+impl BotProcess {
+    pub fn spawn(command: &str, options: &BotOptions) -> Result<Self, HJError> {
+        let start_error = |e: io::Error| HJError::ActorError(format!("Cannot start bot '{}': {}", command, e));
+
+        let stderr = match &options.log_dir {
+            Some(_) => Stdio::piped(),
+            None => Stdio::inherit(),
+        };
+        let mut child = Command::new(command).stdin(Stdio::piped())
+                                             .stdout(Stdio::piped())
+                                             .stderr(stderr)
+                                             .spawn()
+                                             .map_err(start_error)?;
+
+        if let Some(log_dir) = &options.log_dir {
+            let name = Path::new(command).file_name().map_or(command.into(), |n| n.to_string_lossy());
+            let log_path = Path::new(log_dir).join(format!("{}.{}.log", name, child.id()));
+            let mut log = fs::create_dir_all(log_dir).and_then(|_| File::create(&log_path)).map_err(|e| {
+                let _ = child.kill();
+                HJError::ActorError(format!("Cannot create bot log {}: {}", log_path.display(), e))
+            })?;
+            let mut stderr = child.stderr.take().expect("The child's stderr should be piped.");
+            thread::spawn(move || { let _ = io::copy(&mut stderr, &mut log); });
+        }
+
+        let stdin = child.stdin.take().expect("The child's stdin should be piped.");
+        let stdout = BufReader::new(child.stdout.take().expect("The child's stdout should be piped."));
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                let failed = line.is_err();
+                if sender.send(line).is_err() || failed { break; }
+            }
+        });
+
+        Ok(Self { command: command.to_owned(), options: options.clone(), child, stdin: Some(stdin), lines })
+    }
+
+    // Gives a crashing child a moment to exit, so its status can be included in the error.
+    fn exit_status(&mut self) -> String {
+        for _ in 0..10 {
+            match self.child.try_wait() {
+                Ok(Some(status)) => return format!(" ({})", status),
+                Ok(None) => thread::sleep(Duration::from_millis(10)),
+                Err(_) => break,
+            }
+        }
+        "".to_owned()
+    }
+}
+
+impl BotConnection for BotProcess {
+    fn describe(&self) -> String { self.command.clone() }
+
+    fn send(&mut self, line: &str) -> Result<(), HJError> {
+        let result = match self.stdin.as_mut() {
+            Some(stdin) => stdin.write_all(line.as_bytes()).and_then(|_| stdin.write_all(b"\n")).and_then(|_| stdin.flush()),
+            None => Err(io::Error::new(io::ErrorKind::BrokenPipe, "input already closed")),
+        };
+
+        result.map_err(|error| HJError::ActorError(format!("Cannot write to bot: {}{}", error, self.exit_status())))
+    }
+
+    fn receive(&mut self, timeout: Duration) -> Result<String, HJError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(Ok(line)) if line.trim().is_empty() => continue,
+                Ok(Ok(line)) => return Ok(line),
+                Ok(Err(error)) => return Err(HJError::ActorError(format!("Cannot read from bot: {}{}", error, self.exit_status()))),
+                Err(RecvTimeoutError::Disconnected) => return Err(HJError::ActorError(format!("Bot closed its output{}.", self.exit_status()))),
+                Err(RecvTimeoutError::Timeout) => return Err(HJError::ActorError(format!("Bot did not reply within {} ms.", timeout.as_millis()))),
+            }
+        }
+    }
+
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    fn reconnect(&mut self) -> Result<(), HJError> {
+        *self = Self::spawn(&self.command, &self.options)?;
+        Ok(())
+    }
+
+    // Closes the bot's input and waits briefly for it to exit before killing it.
+    fn shutdown(&mut self) {
+        self.stdin = None;
+
+        let deadline = Instant::now() + Duration::from_millis(500);
+        while Instant::now() < deadline {
+            match self.child.try_wait() {
+                Ok(None) => thread::sleep(Duration::from_millis(10)),
+                _ => return,
+            }
+        }
+
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl Drop for BotProcess {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,109 @@
+use wasm_bindgen::prelude::*;
+
+use crate::game::data::{Card, PassDirection};
+use crate::game::errors::HJError;
+use crate::internal::session::{GameSession, Phase};
+use crate::rust_actors::util::get_allowed_cards;
+use crate::util::deck::get_seeded_hands;
+
+// Build with `cargo build --lib --release --target wasm32-unknown-unknown --features wasm`, then run `wasm-bindgen` on `hj.wasm`.
+// Cards cross the boundary as strings like `QS` and `10H`, seats as numbers from 0 to 3.
+
+fn js_error(error: HJError) -> JsError {
+    JsError::new(&format!("{:?}", error))
+}
+
+fn cards_to_js(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|c| c.to_string()).collect()
+}
+
+fn check_seat(seat: usize) -> Result<usize, JsError> {
+    if seat < 4 { Ok(seat) } else { Err(JsError::new(&format!("There is no seat {}.", seat))) }
+}
+
+// E.g. `new Game(42, "left", ["", "rule_v1", "rule_v1", "rule_v1"])`; empty bot specs are seats played from JavaScript.
+#[wasm_bindgen(js_name = Game)]
+pub struct WasmGame(GameSession);
+
+#[wasm_bindgen(js_class = Game)]
+impl WasmGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: u32, pass_direction: &str, bots: Vec<String>) -> Result<WasmGame, JsError> {
+        let direction = PassDirection::from_name(pass_direction)
+            .ok_or_else(|| JsError::new(&format!("Unknown pass direction '{}'.", pass_direction)))?;
+        let bots: [String; 4] = bots.try_into().map_err(|_| JsError::new("Expected bot specs for four seats."))?;
+        let bots = bots.map(|spec| Some(spec).filter(|s| !s.is_empty()));
+
+        Ok(Self(GameSession::new(get_seeded_hands(seed as u64), direction, &bots).map_err(js_error)?))
+    }
+
+    // One of `passing`, `playing`, `finished` and `failed`, the last once a bot has failed.
+    pub fn phase(&self) -> String {
+        match self.0.phase() {
+            Phase::Passing => "passing",
+            Phase::Playing => "playing",
+            Phase::Finished => "finished",
+            Phase::Failed => "failed",
+        }.to_owned()
+    }
+
+    #[wasm_bindgen(js_name = passDirection)]
+    pub fn pass_direction(&self) -> String { self.0.pass_direction().name().to_owned() }
+
+    #[wasm_bindgen(js_name = hasBot)]
+    pub fn has_bot(&self, seat: usize) -> Result<bool, JsError> { Ok(self.0.has_bot(check_seat(seat)?)) }
+
+    #[wasm_bindgen(js_name = currentSeat)]
+    pub fn current_seat(&self) -> usize { self.0.game_info().current_pidx() }
+
+    #[wasm_bindgen(js_name = currentRound)]
+    pub fn current_round(&self) -> usize { self.0.game_info().current_round() }
+
+    #[wasm_bindgen(js_name = heartsPlayed)]
+    pub fn hearts_played(&self) -> bool { self.0.game_info().hearts_played() }
+
+    pub fn hand(&self, seat: usize) -> Result<Vec<String>, JsError> {
+        Ok(cards_to_js(self.0.game_info().hands()[check_seat(seat)?].cards()))
+    }
+
+    // The cards of the unfinished trick in the order they were played, starting with `trickLeader`.
+    #[wasm_bindgen(js_name = currentTrick)]
+    pub fn current_trick(&self) -> Vec<String> {
+        self.0.game_info().current_moves().iter().map(|m| m.card().to_string()).collect()
+    }
+
+    #[wasm_bindgen(js_name = trickLeader)]
+    pub fn trick_leader(&self) -> usize {
+        let game_info = self.0.game_info();
+        game_info.current_moves().first().map_or(game_info.current_pidx(), |m| m.pidx())
+    }
+
+    #[wasm_bindgen(js_name = legalCards)]
+    pub fn legal_cards(&self) -> Vec<String> {
+        match self.0.phase() {
+            Phase::Playing => cards_to_js(&get_allowed_cards(self.0.game_info())),
+            _ => vec![],
+        }
+    }
+
+    pub fn pass(&mut self, seat: usize, cards: Vec<String>) -> Result<(), JsError> {
+        let cards = cards.iter().map(|c| c.parse()).collect::<Result<Vec<Card>, HJError>>().map_err(js_error)?;
+        self.0.pass(check_seat(seat)?, cards).map_err(js_error)
+    }
+
+    pub fn play(&mut self, card: &str) -> Result<(), JsError> {
+        self.0.play(card.parse().map_err(js_error)?).map_err(js_error)?;
+        Ok(())
+    }
+
+    // Lets the bot in the current seat play, and returns its card.
+    pub fn advance(&mut self) -> Result<String, JsError> {
+        Ok(self.0.advance().map_err(js_error)?.card().to_string())
+    }
+
+    // The points taken so far, or the final result once the game is finished.
+    pub fn scores(&self) -> Vec<i32> {
+        let game_info = self.0.game_info();
+        game_info.result().unwrap_or_else(|| game_info.score()).iter().map(|s| *s as i32).collect()
+    }
+}
@@ -0,0 +1,192 @@
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::{Message, WebSocket};
+
+use crate::api::connection::BotConnection;
+use crate::api::json::JSONActor;
+use crate::api::supervisor::BotOptions;
+use crate::game::actor::Actor;
+use crate::game::errors::HJError;
+use crate::internal::actor_spec::create_actor;
+
+// People take their time, so browser seats wait much longer for a reply than bots do.
+pub const HUMAN_TIMEOUT: Duration = Duration::from_secs(3600);
+// The actor spec for a seat played from a browser.
+pub const WEB_SPEC: &str = "web";
+
+const PAGE: &str = include_str!("web/index.html");
+
+pub type WebActor = JSONActor<WebSeat>;
+
+// A browser tab playing a seat: the page speaks the JSON protocol over a WebSocket, one message per line.
+pub struct WebSeat {
+    peer: String,
+    socket: WebSocket<TcpStream>,
+    closed: bool,
+}
+
+impl BotConnection for WebSeat {
+    fn describe(&self) -> String { format!("browser at {}", self.peer) }
+
+    fn send(&mut self, line: &str) -> Result<(), HJError> {
+        if let Err(error) = self.socket.send(Message::text(line)) {
+            self.closed = true;
+            return Err(HJError::ActorError(format!("Cannot write to the browser at {}: {}", self.peer, error)));
+        }
+
+        Ok(())
+    }
+
+    fn receive(&mut self, timeout: Duration) -> Result<String, HJError> {
+        self.socket.get_ref().set_read_timeout(Some(timeout.max(Duration::from_millis(1))))
+            .map_err(|e| HJError::ActorError(format!("Cannot use connection to the browser at {}: {}", self.peer, e)))?;
+
+        loop {
+            match self.socket.read() {
+                Ok(Message::Text(text)) if text.trim().is_empty() => continue,
+                Ok(Message::Text(text)) => return Ok(text.to_string()),
+                Ok(Message::Close(_)) | Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                    self.closed = true;
+                    return Err(HJError::ActorError(format!("The browser at {} left the table.", self.peer)));
+                },
+                Ok(_) => continue,
+                Err(tungstenite::Error::Io(error)) if matches!(error.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+                    return Err(HJError::ActorError(format!("The browser at {} did not reply within {} s.", self.peer, timeout.as_secs())));
+                },
+                Err(error) => {
+                    self.closed = true;
+                    return Err(HJError::ActorError(format!("Cannot read from the browser at {}: {}", self.peer, error)));
+                },
+            }
+        }
+    }
+
+    fn is_alive(&mut self) -> bool { !self.closed }
+
+    fn reconnect(&mut self) -> Result<(), HJError> {
+        Err(HJError::ActorError(format!("The browser at {} has to open the page again.", self.peer)))
+    }
+
+    fn shutdown(&mut self) {
+        let _ = self.socket.close(None);
+        let _ = self.socket.flush();
+        self.closed = true;
+    }
+}
+
+// Serves the table page, and seats each browser that opens it.
+pub struct WebServer {
+    listener: TcpListener,
+    options: BotOptions,
+}
+
+impl WebServer {
+    pub fn bind(address: &str, options: BotOptions) -> Result<Self, HJError> {
+        let listener = TcpListener::bind(address).map_err(|e| HJError::ActorError(format!("Cannot listen on {}: {}", address, e)))?;
+        Ok(Self { listener, options })
+    }
+
+    pub fn local_address(&self) -> String {
+        self.listener.local_addr().map_or("http://?/".to_owned(), |a| format!("http://{}/", a))
+    }
+
+    // Answers page requests until a browser opens the game's WebSocket.
+    // The large error is tungstenite's handshake response, whose type is not ours to choose.
+    #[allow(clippy::result_large_err)]
+    pub fn accept(&self) -> Result<WebActor, HJError> {
+        loop {
+            let (stream, peer) = self.listener.accept().map_err(|e| HJError::ActorError(format!("Cannot accept a connection: {}", e)))?;
+            let path = match Self::request_path(&stream) {
+                Ok(path) => path,
+                Err(error) => { self.options.event(&format!("Ignoring a request from {}: {}", peer, error)); continue; },
+            };
+
+            if path != "/ws" {
+                if let Err(error) = Self::respond(stream, &path) { self.options.event(&format!("Cannot answer {}: {}", peer, error)); }
+                continue;
+            }
+
+            let socket = match tungstenite::accept_hdr(stream, |request: &Request, response: Response| {
+                if self.origin_allowed(request) { return Ok(response); }
+                let mut error = ErrorResponse::new(Some("The page must be served by this game.".to_owned()));
+                *error.status_mut() = StatusCode::FORBIDDEN;
+                Err(error)
+            }) {
+                Ok(socket) => socket,
+                Err(error) => { self.options.event(&format!("Ignoring a WebSocket from {}: {}", peer, error)); continue; },
+            };
+            let options = BotOptions { timeout: HUMAN_TIMEOUT, ..self.options.clone() };
+            return WebActor::connect(WebSeat { peer: peer.to_string(), socket, closed: false }, &options);
+        }
+    }
+
+    // Creates the bots, and waits for a browser in every `web` seat.
+    pub fn seat_actors(&self, specs: &[String; 4]) -> Result<Vec<Box<dyn Actor>>, HJError> {
+        let mut actors: Vec<Box<dyn Actor>> = vec![];
+        for (pidx, spec) in specs.iter().enumerate() {
+            if spec == WEB_SPEC {
+                self.options.event(&format!("Waiting for a browser to take seat {} at {}", pidx, self.local_address()));
+                actors.push(Box::new(self.accept()?));
+                self.options.event(&format!("Seat {} taken.", pidx));
+            } else {
+                actors.push(create_actor(spec, &self.options)?);
+            }
+        }
+
+        Ok(actors)
+    }
+
+    // Browsers send the page's origin with every WebSocket handshake, so this keeps other sites the user
+    // has open from taking a seat. The `Host` header proves nothing, as a rebound DNS name controls it
+    // too; the page must come from the address we listen on or from this machine by its loopback names.
+    // A server listening on every interface is therefore only playable through the loopback names.
+    // Clients without an `Origin` are not browsers and are let in.
+    fn origin_allowed(&self, request: &Request) -> bool {
+        let Some(origin) = request.headers().get("Origin") else { return true };
+        let Ok(address) = self.listener.local_addr() else { return false };
+
+        let mut allowed = vec![format!("http://{}", address)];
+        allowed.extend(["localhost", "127.0.0.1", "[::1]"].map(|host| format!("http://{}:{}", host, address.port())));
+        origin.to_str().is_ok_and(|origin| allowed.iter().any(|a| a.eq_ignore_ascii_case(origin)))
+    }
+
+    // Reads the request line without consuming it, so a WebSocket handshake can still read the whole request.
+    fn request_path(stream: &TcpStream) -> io::Result<String> {
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        let mut buffer = [0; 4096];
+        let mut waited = 0;
+        loop {
+            let length = stream.peek(&mut buffer)?;
+            let head = String::from_utf8_lossy(&buffer[..length]);
+            if head.contains("\r\n\r\n") || length == buffer.len() {
+                stream.set_read_timeout(None)?;
+                let mut words = head.lines().next().unwrap_or("").split_whitespace();
+                return match (words.next(), words.next()) {
+                    (Some("GET"), Some(path)) => Ok(path.to_owned()),
+                    _ => Err(io::Error::new(io::ErrorKind::InvalidData, "not a GET request")),
+                };
+            }
+            if length == 0 || waited >= 5000 { return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "incomplete request")); }
+            std::thread::sleep(Duration::from_millis(10));
+            waited += 10;
+        }
+    }
+
+    fn respond(mut stream: TcpStream, path: &str) -> io::Result<()> {
+        // The request itself is not needed any more; drain what arrived so closing does not reset the connection.
+        let mut request = [0; 4096];
+        let _ = stream.read(&mut request)?;
+
+        let (status, body) = match path {
+            "/" | "/index.html" => ("200 OK", PAGE),
+            _ => ("404 Not Found", "Not found"),
+        };
+        let content_type = if status.starts_with("200") { "text/html; charset=utf-8" } else { "text/plain" };
+        write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, content_type, body.len(), body)?;
+        stream.flush()
+    }
+}
@@ -1,16 +1,17 @@
 use crate::game::data::{Move, PassDirection};
+use crate::game::errors::HJError;
 use super::data::Card;
 
 pub trait Actor {
-    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>);
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError>;
 
-    fn play_card(&mut self, played_moves: &Vec<Move>) -> Card;
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError>;
 
-    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>);
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError>;
 
-    fn end_game(&mut self, score: [isize; 4]);
+    fn end_game(&mut self, score: [isize; 4]) -> Result<(), HJError>;
 
-    fn get_pass(&mut self, direction: PassDirection) -> Vec<Card>;
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError>;
 
-    fn end_pass(&mut self, passed_cards: &Vec<Card>);
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError>;
 }
@@ -2,6 +2,8 @@ use core::clone::Clone;
 
 use num_derive::FromPrimitive;
 
+use crate::game::errors::HJError;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum PassDirection {
     Left,
@@ -59,6 +61,25 @@ impl PassDirection {
         }
     }
 
+    pub fn name(&self) -> &'static str {
+        match self {
+            PassDirection::None => "none",
+            PassDirection::Left => "left",
+            PassDirection::Right => "right",
+            PassDirection::Cross => "cross",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(PassDirection::None),
+            "left" => Some(PassDirection::Left),
+            "right" => Some(PassDirection::Right),
+            "cross" => Some(PassDirection::Cross),
+            _ => None,
+        }
+    }
+
     pub fn from_round(num: usize) -> Self {
         match num % 4 {
             0 => PassDirection::Left,
@@ -143,6 +164,32 @@ impl Card {
     }
 }
 
+// Accepts both `QS`/`10H` and the `♠Q` form used by `Debug`.
+impl std::str::FromStr for Card {
+    type Err = HJError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let invalid = || HJError::InvalidCardError(format!("Cannot parse card '{}'.", string));
+        let string = string.trim();
+        let first = string.chars().next().ok_or_else(invalid)?;
+        let last = string.chars().last().ok_or_else(invalid)?;
+
+        let (suit, rank) = match Suit::from_char(first) {
+            Some(suit) if string.len() > first.len_utf8() => (suit, &string[first.len_utf8()..]),
+            _ => (Suit::from_char(last).ok_or_else(invalid)?, &string[..string.len() - last.len_utf8()]),
+        };
+
+        Ok(Card(Rank::from_name(rank).ok_or_else(invalid)?, suit))
+    }
+}
+
+// Writes the ASCII form accepted by `FromStr`, e.g. `QS` or `10H`.
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{:?}{}", self.rank(), self.suit().letter())
+    }
+}
+
 impl std::fmt::Debug for Card {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         write!(f, "{:?}{:?}", self.suit(), self.rank())?;
@@ -166,6 +213,16 @@ impl Rank {
     pub fn from_index(idx: u8) -> Self {
         num::FromPrimitive::from_u8(idx + 2).unwrap()
     }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "J" => Some(Self::Jack),
+            "Q" => Some(Self::Queen),
+            "K" => Some(Self::King),
+            "A" => Some(Self::Ace),
+            number => number.parse::<u8>().ok().filter(|n| (2..=10).contains(n)).and_then(num::FromPrimitive::from_u8),
+        }
+    }
 }
 
 impl std::fmt::Debug for Rank {
@@ -198,6 +255,25 @@ impl Suit {
     pub fn to_index(&self) -> usize {
         *self as usize - 1
     }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            'S' | '♠' => Some(Self::Spades),
+            'C' | '♣' => Some(Self::Clubs),
+            'D' | '♢' | '♦' => Some(Self::Diamonds),
+            'H' | '♡' | '♥' => Some(Self::Hearts),
+            _ => None,
+        }
+    }
+
+    pub fn letter(&self) -> char {
+        match self {
+            Self::Spades => 'S',
+            Self::Clubs => 'C',
+            Self::Diamonds => 'D',
+            Self::Hearts => 'H',
+        }
+    }
 }
 
 impl std::fmt::Debug for Suit {
@@ -1,4 +1,10 @@
 #[derive(Debug)]
 pub enum HJError {
     InvalidCardError(String),
+    InvalidSpecError(String),
+    InvalidDealError(String),
+    ActorError(String),
+    ProtocolError(String),
+    WrongPhaseError(String),
+    OutputError(String),
 }
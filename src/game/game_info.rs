@@ -4,7 +4,6 @@ use crate::game::data::{Hand, Move};
 use crate::game::errors::HJError;
 use crate::game::validator::{NoValidator, Validator};
 use crate::internal::hj_validator::HJValidator;
-use crate::rust_actors::actor_human::ActorHuman;
 use crate::util::deck::find_winner_pidx;
 
 #[derive(Clone)]
@@ -117,13 +116,15 @@ impl GameInfo {
     }
 
     pub fn pass<V: Validator, T: Actor + ?Sized>(&mut self, direction: PassDirection, actors: &mut [&mut T; 4]) -> Result<(), HJError> {
-        let passed_cards: [Vec<Card>; 4] = [0, 1, 2, 3].map(|pidx|
-            actors[pidx].get_pass(direction)
-        );
+        let passed_cards: [Vec<Card>; 4] = [
+            actors[0].get_pass(direction)?,
+            actors[1].get_pass(direction)?,
+            actors[2].get_pass(direction)?,
+            actors[3].get_pass(direction)?,
+        ];
 
-        for pidx in 0..4 { V::validate_pass(direction, &passed_cards[pidx], pidx, self)?; }
-        self.did_pass(direction, &passed_cards);
-        for pidx in 0..4 { actors[(pidx + direction.index_shift()) % 4].end_pass(&passed_cards[pidx]); }
+        self.apply_pass::<V>(direction, &passed_cards)?;
+        for pidx in 0..4 { actors[(pidx + direction.index_shift()) % 4].end_pass(&passed_cards[pidx])?; }
 
         Ok(())
     }
@@ -137,7 +138,7 @@ impl GameInfo {
                 if stop == StopCondition::Until(self.current_pidx) && done_move { return Ok(()); }
                 let new_move = Move(
                     self.current_pidx,
-                    actors[self.current_pidx].play_card(&self.current_moves),
+                    actors[self.current_pidx].play_card(&self.current_moves)?,
                 );
 
                 V::validate_move(self, &self.current_moves, new_move)?;
@@ -147,13 +148,32 @@ impl GameInfo {
 
             let winner_pidx = find_winner_pidx(&self.current_moves);
             for actor in actors.iter_mut() {
-                actor.end_round(winner_pidx, &self.current_moves);
+                actor.end_round(winner_pidx, &self.current_moves)?;
             }
             self.did_play_round(winner_pidx);
         }
 
         self.did_play_game();
-        for actor in actors.iter_mut() { actor.end_game(self.result().unwrap()); }
+        for actor in actors.iter_mut() { actor.end_game(self.result().unwrap())?; }
+        Ok(())
+    }
+
+    pub fn apply_pass<V: Validator>(&mut self, direction: PassDirection, passed_cards: &[Vec<Card>; 4]) -> Result<(), HJError> {
+        for pidx in 0..4 { V::validate_pass(direction, &passed_cards[pidx], pidx, self)?; }
+        self.did_pass(direction, passed_cards);
+        Ok(())
+    }
+
+    pub fn apply_move<V: Validator>(&mut self, card: Card) -> Result<(), HJError> {
+        let new_move = Move(self.current_pidx, card);
+        V::validate_move(self, &self.current_moves, new_move)?;
+        self.did_play_move(new_move);
+
+        if self.current_moves.len() == 4 {
+            self.did_play_round(find_winner_pidx(&self.current_moves));
+            if self.current_round == 13 { self.did_play_game(); }
+        }
+
         Ok(())
     }
 
@@ -1,2 +1,11 @@
-pub(crate) mod game;
+pub mod actor_spec;
+pub mod bench;
+pub mod game;
 pub mod hj_validator;
+pub mod record;
+pub mod results;
+// The turn-by-turn game the C and JavaScript bindings drive.
+#[cfg(any(feature = "ffi", feature = "wasm"))]
+pub mod session;
+pub mod stats;
+pub mod tournament;
@@ -0,0 +1,71 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::api::json::JSONActor;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::api::socket::{BotSocket, SocketActor};
+use crate::api::supervisor::BotOptions;
+use crate::game::actor::Actor;
+use crate::game::errors::HJError;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::rust_actors::actor_human::ActorHuman;
+use crate::rust_actors::actor_ismcts_v1::{ActorISMCTSV1, Observers};
+use crate::rust_actors::actor_mcts_mod_v1::ActorMCTSModV1;
+use crate::rust_actors::actor_mcts_v1::ActorMCTSV1;
+use crate::rust_actors::actor_random::ActorRandom;
+use crate::rust_actors::actor_rule_v1::ActorRuleV1;
+use crate::rust_actors::determinize::determinize_v2::DeterminizeV2;
+use crate::rust_actors::eval_round::eval_round_v1::EvalRoundV1;
+use crate::rust_actors::eval_state::eval_state_v1::EvalStateV1;
+use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
+
+type RuleV1 = ActorRuleV1<EvalRoundV1, EvalStateV1, ExtendedPlayerState>;
+
+// Specs look like `name[:arg[:arg]]`, e.g. `rule_v1`, `mcts_v1:50:10`, `human:kibitz`, `json:./bot.py` or `socket:tcp:localhost:4000`.
+// The searches take a last `v2` argument, e.g. `ismcts_v1:500:v2`, to deal the unseen cards with `DeterminizeV2`.
+#[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+pub fn create_actor(spec: &str, bot_options: &BotOptions) -> Result<Box<dyn Actor>, HJError> {
+    let (name, args) = match spec.split_once(':') {
+        Some((name, args)) => (name, args),
+        None => (spec, ""),
+    };
+    let (number_args, determinize_v2) = match (name, args.rsplit_once(':')) {
+        ("mcts_v1" | "ismcts_v1" | "ismcts_mo_v1", Some((rest, "v2"))) => (rest, true),
+        ("mcts_v1" | "ismcts_v1" | "ismcts_mo_v1", None) if args == "v2" => ("", true),
+        _ => (args, false),
+    };
+    let numbers = |defaults: &[usize]| -> Result<Vec<usize>, HJError> {
+        let given: Vec<&str> = if number_args.is_empty() { vec![] } else { number_args.split(':').collect() };
+        if given.len() > defaults.len() {
+            return Err(HJError::InvalidSpecError(format!("Too many arguments in actor spec '{}'.", spec)));
+        }
+        defaults.iter().enumerate().map(|(i, default)| match given.get(i) {
+            Some(arg) => arg.parse().map_err(|_| HJError::InvalidSpecError(format!("Invalid number '{}' in actor spec '{}'.", arg, spec))),
+            None => Ok(*default),
+        }).collect()
+    };
+
+    Ok(match name {
+        #[cfg(not(target_arch = "wasm32"))]
+        "human" if args == "kibitz" => Box::new(ActorHuman::with_kibitzer()),
+        #[cfg(not(target_arch = "wasm32"))]
+        "human" if args.is_empty() => Box::new(ActorHuman::new()),
+        "random" => Box::new(ActorRandom::new()),
+        "rule_v1" => Box::new(ActorRuleV1::new()),
+        "mcts_v1" if determinize_v2 => { let n = numbers(&[50, 10])?; Box::new(ActorMCTSV1::<DeterminizeV2, EvalStateV1, RuleV1, ExtendedPlayerState>::customize(n[0], n[1])) },
+        "mcts_v1" => { let n = numbers(&[50, 10])?; Box::new(ActorMCTSV1::new(n[0], n[1])) },
+        "mcts_mod_v1" => { let n = numbers(&[50])?; Box::new(ActorMCTSModV1::new(n[0])) },
+        "ismcts_v1" | "ismcts_mo_v1" if determinize_v2 => {
+            let n = numbers(&[500])?;
+            let observers = if name == "ismcts_v1" { Observers::Single } else { Observers::Multi };
+            Box::new(ActorISMCTSV1::<DeterminizeV2, EvalStateV1, RuleV1, ExtendedPlayerState>::customize(n[0], observers))
+        },
+        "ismcts_v1" => { let n = numbers(&[500])?; Box::new(ActorISMCTSV1::new(n[0])) },
+        "ismcts_mo_v1" => { let n = numbers(&[500])?; Box::new(ActorISMCTSV1::multi_observer(n[0])) },
+        #[cfg(not(target_arch = "wasm32"))]
+        "json" if !args.is_empty() => Box::new(JSONActor::with_options(args.to_owned(), bot_options.clone())?),
+        #[cfg(not(target_arch = "wasm32"))]
+        "socket" if !args.is_empty() => Box::new(SocketActor::connect(BotSocket::connect(args)?, bot_options)?),
+        #[cfg(target_arch = "wasm32")]
+        "human" | "json" | "socket" => return Err(HJError::InvalidSpecError(format!("Actor spec '{}' needs a terminal, processes or sockets, which this build lacks.", spec))),
+        _ => return Err(HJError::InvalidSpecError(format!("Unknown actor spec '{}'.", spec))),
+    })
+}
@@ -0,0 +1,192 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::time::Duration;
+
+use crate::rust_actors::actor_rule_v1::ActorRuleV1;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
+use crate::rust_actors::determinize::determinize_v1::DeterminizeV1;
+use crate::rust_actors::eval_round::eval_round_v1::EvalRoundV1;
+use crate::rust_actors::eval_state::eval_state_v1::EvalStateV1;
+use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
+use crate::game::data::{Card, Hand, Move, PassDirection};
+use crate::game::game_info::{GameInfo, StopCondition};
+use crate::internal::game::play_with_hands;
+use crate::internal::hj_validator::HJValidator;
+use crate::rust_actors::actor_ismcts_v1::ismcts::{ismcts, Observers};
+use crate::rust_actors::actor_mcts_mod_v1::mcts_mod::mcts_mod;
+use crate::rust_actors::actor_mcts_v1::mcts::mcts;
+use crate::rust_actors::determinize::Determinize;
+use crate::rust_actors::determinize::determinize_v2::DeterminizeV2;
+use crate::rust_actors::player_state::MediasResActor;
+use crate::rust_actors::util::get_allowed_cards;
+use crate::util::deck::{find_winner_pidx, get_seeded_hands};
+use crate::util::platform::Instant;
+use crate::util::rng;
+
+type SubActor = ActorRuleV1<EvalRoundV1, EvalStateV1, ExtendedPlayerState>;
+
+pub struct BenchResult {
+    name: &'static str,
+    operations: usize,
+    elapsed: Duration,
+}
+
+impl BenchResult {
+    pub fn per_second(&self) -> f64 {
+        self.operations as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+#[derive(Clone)]
+struct Position {
+    game_info: GameInfo,
+    player_states: [DefaultPlayerState; 4],
+}
+
+// Positions are reached by always playing the first allowed card, so they only depend on the seed.
+fn positions(seed: u64, deals: usize) -> Vec<Position> {
+    let mut positions = vec![];
+    for deal in 0..deals {
+        let mut game_info = GameInfo::new(get_seeded_hands(seed.wrapping_add(deal as u64)));
+        let mut history: Vec<Move> = vec![];
+
+        while game_info.result().is_none() {
+            positions.push(Position {
+                player_states: [0, 1, 2, 3].map(|pidx| player_state(&game_info, &history, pidx)),
+                game_info: game_info.clone(),
+            });
+
+            let card = get_allowed_cards(&game_info)[0];
+            history.push(Move(game_info.current_pidx(), card));
+            game_info.apply_move::<HJValidator>(card).expect("The first allowed card should be valid.");
+        }
+    }
+    positions
+}
+
+fn player_state(game_info: &GameInfo, history: &[Move], pidx: usize) -> DefaultPlayerState {
+    let mut cards_in_game = [[true; 13]; 4];
+    let mut still_has = [[true; 4]; 4];
+    // Whether a seat took any scoring card, even if its points cancel out.
+    let mut scored = [false; 4];
+    for trick in history.chunks(4) {
+        let first_suit = trick[0].card().suit();
+        for Move(move_pidx, Card(rank, suit)) in trick {
+            cards_in_game[suit.to_index()][rank.to_index()] = false;
+            if *suit != first_suit { still_has[first_suit.to_index()][*move_pidx] = false; }
+        }
+        if trick.len() == 4 && trick.iter().any(|m| m.card().score() != 0) {
+            scored[find_winner_pidx(&trick.to_vec())] = true;
+        }
+    }
+
+    DefaultPlayerState::new(
+        game_info.hands()[pidx].cards().clone(),
+        game_info.first_round(),
+        game_info.hearts_played(),
+        pidx,
+        cards_in_game,
+        game_info.score(),
+        scored,
+        still_has,
+    )
+}
+
+// The searches draw from a generator seeded afresh for every measurement, so runs make the same random choices.
+fn measure<T, F: FnMut(&T) -> usize>(name: &'static str, seed: u64, inputs: &[T], duration: Duration, mut f: F) -> BenchResult {
+    rng::seed(seed);
+    let start = Instant::now();
+    let mut operations = 0;
+    for input in inputs.iter().cycle() {
+        operations += f(input);
+        if start.elapsed() >= duration { break; }
+    }
+
+    BenchResult { name, operations, elapsed: start.elapsed() }
+}
+
+pub fn run_benchmarks(seed: u64, duration: Duration) -> Vec<BenchResult> {
+    let positions = positions(seed, 8);
+    let trick_starts: Vec<Position> = positions.iter()
+        .filter(|p| p.game_info.current_moves().is_empty() && p.game_info.current_round() < 10)
+        .cloned()
+        .collect();
+    // Whole games, from the deal through the pass and all 13 tricks, as a tournament plays them.
+    let deals: Vec<([Hand; 4], PassDirection)> = (0..8)
+        .map(|deal| (get_seeded_hands(seed.wrapping_add(deal as u64)), PassDirection::from_round(deal)))
+        .collect();
+
+    vec![
+        measure("get_allowed_cards", seed, &positions, duration, |p| { get_allowed_cards(&p.game_info); 1 }),
+        measure("determinize_v1", seed, &positions, duration, |p| {
+            let pidx = p.game_info.current_pidx();
+            DeterminizeV1::determinize(pidx, &p.player_states[pidx], p.game_info.current_moves());
+            1
+        }),
+        measure("determinize_v2", seed, &positions, duration, |p| {
+            let pidx = p.game_info.current_pidx();
+            DeterminizeV2::determinize(pidx, &p.player_states[pidx], p.game_info.current_moves());
+            1
+        }),
+        measure("mcts_v1_rollouts", seed, &trick_starts, duration, |p| {
+            mcts::<SubActor>(&p.game_info, &p.player_states, 50).iter().map(|(_, _, visits)| visits).sum()
+        }),
+        measure("mcts_mod_v1_rollouts", seed, &trick_starts, duration, |p| {
+            let pidx = p.game_info.current_pidx();
+            mcts_mod::<DeterminizeV1, SubActor, _>(pidx, &p.player_states[pidx], p.game_info.current_moves(), 50).iter().map(|(_, _, visits)| visits).sum()
+        }),
+        measure("ismcts_v1_rollouts", seed, &trick_starts, duration, |p| {
+            let pidx = p.game_info.current_pidx();
+            ismcts::<DeterminizeV1, SubActor, _>(pidx, &p.player_states[pidx], p.game_info.current_moves(), 50, Observers::Single).iter().map(|(_, _, visits)| visits).sum()
+        }),
+        measure("rule_v1_playouts", seed, &trick_starts, duration, |p| {
+            let mut game_info = p.game_info.clone();
+            let mut actors = p.player_states.clone().map(|ps| SubActor::new_from_player_state(&ps));
+            let [a0, a1, a2, a3] = &mut actors;
+            game_info.play_without_validator(&mut [a0, a1, a2, a3], StopCondition::None);
+            1
+        }),
+        measure("rule_v1_games", seed, &deals, duration, |(hands, pass_direction)| {
+            let mut actors = [SubActor::new(), SubActor::new(), SubActor::new(), SubActor::new()];
+            let [a0, a1, a2, a3] = &mut actors;
+            play_with_hands(hands.clone(), *pass_direction, [a0, a1, a2, a3]).expect("Rule actors should finish their games.");
+            1
+        }),
+    ]
+}
+
+pub fn print_results(results: &[BenchResult], baseline_path: Option<&str>) -> std::io::Result<()> {
+    let baseline = match baseline_path {
+        Some(path) => read_latest(path)?,
+        None => json::JsonValue::Null,
+    };
+
+    for result in results {
+        let line = format!("{:24} {:>14.1}/s", result.name, result.per_second());
+        match baseline["results"][result.name].as_f64() {
+            Some(previous) if previous > 0. => println!("{}   ({:+.1}% vs {})", line, 100. * (result.per_second() / previous - 1.), baseline["label"]),
+            _ => println!("{}", line),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn append_results(path: &str, label: &str, seed: u64, results: &[BenchResult]) -> std::io::Result<()> {
+    let mut values = json::JsonValue::new_object();
+    for result in results { values[result.name] = result.per_second().into(); }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", json::stringify(json::object!{ label: label, seed: seed, results: values }))
+}
+
+fn read_latest(path: &str) -> std::io::Result<json::JsonValue> {
+    let last_line = BufReader::new(File::open(path)?).lines()
+        .collect::<std::io::Result<Vec<String>>>()?
+        .into_iter()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or_default();
+
+    Ok(json::parse(&last_line).unwrap_or(json::JsonValue::Null))
+}
@@ -1,22 +1,42 @@
 use core::result::Result;
 use core::result::Result::Ok;
 use crate::game::actor::Actor;
-use crate::game::data::{Card, Hand, PassDirection, Rank, Suit};
+use crate::game::data::{Hand, PassDirection};
 use crate::game::errors::HJError;
 use crate::game::game_info::{GameInfo, StopCondition};
 use crate::internal::hj_validator::HJValidator;
+use crate::internal::record::{GameRecord, RecordingActor};
 
-#[allow(dead_code)]
-pub fn play_with_hands(hands: [Hand; 4], pass_direction: PassDirection, mut actors: [&mut dyn Actor; 4]) -> Result<[isize; 4], HJError> {
-    actors[0].initialize(0, hands[0].cards());
-    actors[1].initialize(1, hands[1].cards());
-    actors[2].initialize(2, hands[2].cards());
-    actors[3].initialize(3, hands[3].cards());
+pub fn play_with_hands(hands: [Hand; 4], pass_direction: PassDirection, actors: [&mut dyn Actor; 4]) -> Result<[isize; 4], HJError> {
+    Ok(play_recorded(hands, pass_direction, actors)?.scores())
+}
+
+pub fn play_recorded(hands: [Hand; 4], pass_direction: PassDirection, actors: [&mut dyn Actor; 4]) -> Result<GameRecord, HJError> {
+    let [a0, a1, a2, a3] = actors;
+    let mut recorders = [
+        RecordingActor::new(a0),
+        RecordingActor::new(a1),
+        RecordingActor::new(a2),
+        RecordingActor::new(a3),
+    ];
+
+    for pidx in 0..4 { recorders[pidx].initialize(pidx, hands[pidx].cards())?; }
 
-    let mut game_info = GameInfo::new(hands);
+    let mut game_info = GameInfo::new(hands.clone());
+    {
+        let [r0, r1, r2, r3] = &mut recorders;
+        let mut actors: [&mut dyn Actor; 4] = [r0, r1, r2, r3];
 
-    game_info.pass::<HJValidator, dyn Actor>(pass_direction, &mut actors)?;
-    game_info.play::<HJValidator, dyn Actor>(&mut actors, StopCondition::None)?;
+        game_info.pass::<HJValidator, dyn Actor>(pass_direction, &mut actors)?;
+        game_info.play::<HJValidator, dyn Actor>(&mut actors, StopCondition::None)?;
+    }
 
-    Ok(game_info.score())
+    Ok(GameRecord::new(
+        hands,
+        pass_direction,
+        [0, 1, 2, 3].map(|pidx| recorders[pidx].pass().clone()),
+        recorders[0].tricks().clone(),
+        game_info.score(),
+        [0, 1, 2, 3].map(|pidx| recorders[pidx].think_time()),
+    ))
 }
@@ -0,0 +1,259 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+
+use crate::game::actor::Actor;
+use crate::game::data::{Card, Hand, Move, PassDirection, Rank, Suit};
+use crate::game::errors::HJError;
+use crate::util::platform::Instant;
+
+#[derive(Clone, Debug)]
+pub struct Trick {
+    moves: Vec<Move>,
+    winner_pidx: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct GameRecord {
+    hands: [Hand; 4],
+    pass_direction: PassDirection,
+    passes: [Vec<Card>; 4],
+    tricks: Vec<Trick>,
+    scores: [isize; 4],
+    think_time: [Duration; 4],
+}
+
+pub(crate) struct RecordingActor<'a> {
+    actor: &'a mut dyn Actor,
+    pass: Vec<Card>,
+    tricks: Vec<Trick>,
+    think_time: Duration,
+}
+
+impl Trick {
+    pub fn new(moves: Vec<Move>, winner_pidx: usize) -> Self {
+        Self { moves, winner_pidx }
+    }
+
+    pub fn moves(&self) -> &Vec<Move> { &self.moves }
+
+    pub fn winner_pidx(&self) -> usize { self.winner_pidx }
+
+    pub fn points(&self) -> isize {
+        self.moves.iter().map(|m| m.card().score()).sum()
+    }
+}
+
+impl GameRecord {
+    pub fn new(hands: [Hand; 4], pass_direction: PassDirection, passes: [Vec<Card>; 4], tricks: Vec<Trick>, scores: [isize; 4], think_time: [Duration; 4]) -> Self {
+        Self { hands, pass_direction, passes, tricks, scores, think_time }
+    }
+
+    pub fn hands(&self) -> &[Hand; 4] { &self.hands }
+
+    pub fn pass_direction(&self) -> PassDirection { self.pass_direction }
+
+    pub fn passes(&self) -> &[Vec<Card>; 4] { &self.passes }
+
+    pub fn tricks(&self) -> &Vec<Trick> { &self.tricks }
+
+    pub fn scores(&self) -> [isize; 4] { self.scores }
+
+    pub fn think_time(&self) -> [Duration; 4] { self.think_time }
+
+    pub fn points_taken(&self) -> [isize; 4] {
+        let mut points = [0; 4];
+        for trick in &self.tricks { points[trick.winner_pidx()] += trick.points(); }
+        points
+    }
+
+    // The cards `pidx` plays the game with: the dealt hand less the cards passed away, plus the cards received.
+    pub fn hand_after_pass(&self, pidx: usize) -> Vec<Card> {
+        let from_pidx = (pidx + 4 - self.pass_direction.index_shift()) % 4;
+        self.hands[pidx].cards().iter()
+            .filter(|c| !self.passes[pidx].contains(c))
+            .chain(self.passes[from_pidx].iter())
+            .copied()
+            .collect()
+    }
+
+    pub fn taker_of(&self, card: Card) -> Option<usize> {
+        self.tricks.iter()
+            .find(|t| t.moves().iter().any(|m| m.card() == card))
+            .map(|t| t.winner_pidx())
+    }
+
+    pub fn queen_of_spades_taker(&self) -> Option<usize> {
+        self.taker_of(Card(Rank::Queen, Suit::Spades))
+    }
+
+    pub fn jack_of_diamonds_taker(&self) -> Option<usize> {
+        self.taker_of(Card(Rank::Jack, Suit::Diamonds))
+    }
+
+    pub fn scoring_cards_taken(&self) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for trick in &self.tricks {
+            counts[trick.winner_pidx()] += trick.moves().iter().filter(|m| m.card().score() != 0).count();
+        }
+        counts
+    }
+
+    pub fn hearts_breaker(&self) -> Option<usize> {
+        self.tricks.iter()
+            .flat_map(|t| t.moves().iter())
+            .find(|m| m.card().suit() == Suit::Hearts)
+            .map(|m| m.pidx())
+    }
+
+    // Suits `pidx` was dealt but no longer holds once the cards it received are counted.
+    pub fn suits_voided_by_passing(&self, pidx: usize) -> usize {
+        let after_pass = self.hand_after_pass(pidx);
+        Suit::all().iter().filter(|suit| {
+            self.hands[pidx].cards().iter().any(|c| c.suit() == **suit) && !after_pass.iter().any(|c| c.suit() == **suit)
+        }).count()
+    }
+
+    pub fn moon_shooter(&self) -> Option<usize> {
+        let mut scored = [false; 4];
+        for trick in &self.tricks {
+            scored[trick.winner_pidx()] |= trick.moves().iter().any(|m| m.card().score() != 0);
+        }
+
+        if scored.iter().filter(|b| **b).count() == 1 { scored.iter().position(|b| *b) } else { None }
+    }
+
+    // Everything needed to replay the game; think times are left out.
+    pub fn to_json(&self) -> json::JsonValue {
+        json::object!{
+            pass_direction: self.pass_direction.name(),
+            hands: self.hands.iter().map(|h| serialize_cards(h.cards())).collect::<Vec<_>>(),
+            passes: self.passes.iter().map(|p| serialize_cards(p)).collect::<Vec<_>>(),
+            tricks: self.tricks.iter().map(|t| json::object!{
+                moves: t.moves().iter().map(|m| vec![m.pidx(), m.card().rank() as usize, m.card().suit() as usize]).collect::<Vec<_>>(),
+                winner: t.winner_pidx(),
+            }).collect::<Vec<_>>(),
+            scores: self.scores.to_vec(),
+        }
+    }
+
+    // Only checks the shape of the record; whether the game follows the rules shows when it is replayed.
+    pub fn from_json(value: &json::JsonValue) -> Result<Self, HJError> {
+        let invalid = |reason: &str| HJError::InvalidDealError(format!("{} in game record {}", reason, value.dump()));
+        let four = |value: &json::JsonValue, reason: &str| -> Result<[Vec<Card>; 4], HJError> {
+            let lists = value.members().map(deserialize_cards).collect::<Option<Vec<Vec<Card>>>>().ok_or_else(|| invalid(reason))?;
+            lists.try_into().map_err(|_| invalid(reason))
+        };
+
+        let pass_direction = value["pass_direction"].as_str()
+            .and_then(PassDirection::from_name)
+            .ok_or_else(|| invalid("Invalid pass direction"))?;
+        let hands = four(&value["hands"], "Invalid hands")?;
+        if hands.iter().any(|h| h.len() != 13) { return Err(invalid("Expected 13 cards per hand")); }
+        let mut seen = [[false; 13]; 4];
+        for card in hands.iter().flatten() {
+            if seen[card.suit().to_index()][card.rank().to_index()] { return Err(invalid("Duplicate card")); }
+            seen[card.suit().to_index()][card.rank().to_index()] = true;
+        }
+        let passes = four(&value["passes"], "Invalid passes")?;
+
+        let tricks = value["tricks"].members().map(|trick| {
+            let moves = trick["moves"].members().map(|m| {
+                let pidx = m[0].as_usize().filter(|pidx| *pidx < 4)?;
+                Some(Move(pidx, Card::new(num::FromPrimitive::from_u8(m[1].as_u8()?)?, num::FromPrimitive::from_u8(m[2].as_u8()?)?)))
+            }).collect::<Option<Vec<Move>>>();
+            match (moves, trick["winner"].as_usize()) {
+                (Some(moves), Some(winner)) if moves.len() == 4 && winner < 4 => Ok(Trick::new(moves, winner)),
+                _ => Err(invalid("Invalid trick")),
+            }
+        }).collect::<Result<Vec<Trick>, HJError>>()?;
+
+        let scores: [isize; 4] = value["scores"].members().map(|s| s.as_isize()).collect::<Option<Vec<isize>>>()
+            .and_then(|s| s.try_into().ok())
+            .ok_or_else(|| invalid("Invalid scores"))?;
+
+        Ok(Self::new(hands.map(Hand::new), pass_direction, passes, tricks, scores, [Duration::ZERO; 4]))
+    }
+
+    // A moon is blocked when a single scoring card kept one player from taking all of them.
+    pub fn moon_blocker(&self) -> Option<usize> {
+        let counts = self.scoring_cards_taken();
+        if counts.iter().filter(|c| **c > 0).count() != 2 { return None; }
+
+        match (counts.iter().position(|c| *c == 1), counts.iter().filter(|c| **c > 1).count()) {
+            (Some(pidx), 1) => Some(pidx),
+            _ => None,
+        }
+    }
+}
+
+// Reads the `--records` output of a tournament, one game per line.
+pub fn load_records(path: &str) -> Result<Vec<GameRecord>, HJError> {
+    let file = File::open(path).map_err(|e| HJError::InvalidDealError(format!("Cannot open {}: {}", path, e)))?;
+
+    BufReader::new(file).lines()
+        .map(|line| line.map_err(|e| HJError::InvalidDealError(format!("Cannot read {}: {}", path, e))))
+        .filter(|line| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|line| {
+            let value = json::parse(&line?).map_err(|e| HJError::InvalidDealError(format!("Invalid JSON in {}: {}", path, e)))?;
+            GameRecord::from_json(&value["record"])
+        })
+        .collect()
+}
+
+fn serialize_cards(cards: &[Card]) -> Vec<Vec<usize>> {
+    cards.iter().map(|c| vec![c.rank() as usize, c.suit() as usize]).collect()
+}
+
+fn deserialize_cards(value: &json::JsonValue) -> Option<Vec<Card>> {
+    if !value.is_array() { return None; }
+    value.members().map(|card| Some(Card::new(
+        num::FromPrimitive::from_u8(card[0].as_u8()?)?,
+        num::FromPrimitive::from_u8(card[1].as_u8()?)?,
+    ))).collect()
+}
+
+impl<'a> RecordingActor<'a> {
+    pub fn new(actor: &'a mut dyn Actor) -> Self {
+        Self { actor, pass: vec![], tricks: vec![], think_time: Duration::ZERO }
+    }
+
+    pub fn pass(&self) -> &Vec<Card> { &self.pass }
+
+    pub fn tricks(&self) -> &Vec<Trick> { &self.tricks }
+
+    pub fn think_time(&self) -> Duration { self.think_time }
+}
+
+impl<'a> Actor for RecordingActor<'a> {
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError> {
+        self.actor.initialize(pidx, cards)
+    }
+
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
+        let start = Instant::now();
+        let card = self.actor.play_card(played_moves);
+        self.think_time += start.elapsed();
+        card
+    }
+
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
+        self.tricks.push(Trick::new(played_moves.clone(), winner_pidx));
+        self.actor.end_round(winner_pidx, played_moves)
+    }
+
+    fn end_game(&mut self, score: [isize; 4]) -> Result<(), HJError> {
+        self.actor.end_game(score)
+    }
+
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError> {
+        let start = Instant::now();
+        self.pass = self.actor.get_pass(direction)?;
+        self.think_time += start.elapsed();
+        Ok(self.pass.clone())
+    }
+
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError> {
+        self.actor.end_pass(passed_cards)
+    }
+}
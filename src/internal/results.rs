@@ -0,0 +1,167 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::game::data::PassDirection;
+use crate::game::errors::HJError;
+use crate::internal::record::GameRecord;
+
+pub struct GameResult {
+    game_id: usize,
+    seed: Option<u64>,
+    deal_id: String,
+    pass_direction: PassDirection,
+    deal_seats: [usize; 4],
+    actor_specs: [String; 4],
+    // A game an actor failed in counts as a forfeit: it is reported, but without a record.
+    outcome: Result<GameRecord, HJError>,
+}
+
+pub trait ResultObserver {
+    fn observe(&mut self, result: &GameResult) -> Result<(), HJError>;
+}
+
+pub struct ResultsWriter {
+    csv: Option<BufWriter<File>>,
+    jsonl: Option<BufWriter<File>>,
+    // Full game records for `hj replay`.
+    records: Option<BufWriter<File>>,
+}
+
+impl GameResult {
+    pub fn new(game_id: usize, seed: Option<u64>, deal_id: String, pass_direction: PassDirection, deal_seats: [usize; 4], actor_specs: [String; 4], outcome: Result<GameRecord, HJError>) -> Self {
+        Self { game_id, seed, deal_id, pass_direction, deal_seats, actor_specs, outcome }
+    }
+
+    pub fn game_id(&self) -> usize { self.game_id }
+
+    pub fn actor_specs(&self) -> &[String; 4] { &self.actor_specs }
+
+    pub fn record(&self) -> Option<&GameRecord> { self.outcome.as_ref().ok() }
+
+    pub fn error(&self) -> Option<&HJError> { self.outcome.as_ref().err() }
+
+    fn error_message(&self) -> Option<String> {
+        self.error().map(|e| format!("{:?}", e))
+    }
+
+    fn csv_header() -> String {
+        let mut columns = vec!["game".to_owned(), "seed".to_owned(), "deal_id".to_owned(), "pass_direction".to_owned()];
+        for field in ["actor", "deal_seat", "points", "think_ms"] {
+            for pidx in 0..4 { columns.push(format!("{}_{}", field, pidx)); }
+        }
+        columns.extend(["queen_of_spades_taker", "jack_of_diamonds_taker", "moon_shooter", "error"].map(|c| c.to_owned()));
+        columns.join(",")
+    }
+
+    fn csv_row(&self) -> String {
+        let optional = |pidx: Option<usize>| pidx.map(|p| p.to_string()).unwrap_or_default();
+
+        let mut columns = vec![
+            self.game_id.to_string(),
+            self.seed.map(|s| s.to_string()).unwrap_or_default(),
+            csv_escape(&self.deal_id),
+            self.pass_direction.name().to_owned(),
+        ];
+        columns.extend(self.actor_specs.iter().map(|s| csv_escape(s)));
+        columns.extend(self.deal_seats.map(|s| s.to_string()));
+        match self.record() {
+            Some(record) => {
+                columns.extend(record.scores().map(|s| s.to_string()));
+                columns.extend(think_ms(record).map(|t| format!("{:.3}", t)));
+                columns.push(optional(record.queen_of_spades_taker()));
+                columns.push(optional(record.jack_of_diamonds_taker()));
+                columns.push(optional(record.moon_shooter()));
+            },
+            None => columns.extend(std::iter::repeat_n(String::new(), 11)),
+        }
+        columns.push(csv_escape(&self.error_message().unwrap_or_default()));
+        columns.join(",")
+    }
+
+    fn json(&self) -> json::JsonValue {
+        let mut value = json::object!{
+            game: self.game_id,
+            seed: self.seed,
+            deal_id: self.deal_id.clone(),
+            pass_direction: self.pass_direction.name(),
+            actors: self.actor_specs.to_vec(),
+            deal_seats: self.deal_seats.to_vec(),
+            error: self.error_message(),
+        };
+        if let Some(record) = self.record() {
+            value["points"] = record.scores().to_vec().into();
+            value["think_ms"] = think_ms(record).to_vec().into();
+            value["queen_of_spades_taker"] = record.queen_of_spades_taker().into();
+            value["jack_of_diamonds_taker"] = record.jack_of_diamonds_taker().into();
+            value["moon_shooter"] = record.moon_shooter().into();
+        }
+        value
+    }
+
+    fn record_json(&self, record: &GameRecord) -> json::JsonValue {
+        json::object!{
+            game: self.game_id,
+            deal_id: self.deal_id.clone(),
+            actors: self.actor_specs.to_vec(),
+            record: record.to_json(),
+        }
+    }
+}
+
+impl ResultsWriter {
+    pub fn new(csv_path: Option<&str>, jsonl_path: Option<&str>, records_path: Option<&str>) -> std::io::Result<Self> {
+        let mut csv = match csv_path {
+            Some(path) => Some(BufWriter::new(File::create(path)?)),
+            None => None,
+        };
+        if let Some(csv) = &mut csv { writeln!(csv, "{}", GameResult::csv_header())?; }
+
+        let jsonl = match jsonl_path {
+            Some(path) => Some(BufWriter::new(File::create(path)?)),
+            None => None,
+        };
+
+        let records = match records_path {
+            Some(path) => Some(BufWriter::new(File::create(path)?)),
+            None => None,
+        };
+
+        Ok(Self { csv, jsonl, records })
+    }
+
+    pub fn write(&mut self, result: &GameResult) -> std::io::Result<()> {
+        if let Some(csv) = &mut self.csv {
+            writeln!(csv, "{}", result.csv_row())?;
+            csv.flush()?;
+        }
+        if let Some(jsonl) = &mut self.jsonl {
+            writeln!(jsonl, "{}", json::stringify(result.json()))?;
+            jsonl.flush()?;
+        }
+        // Forfeited games cannot be replayed, so only the finished ones are kept.
+        if let (Some(records), Some(record)) = (&mut self.records, result.record()) {
+            writeln!(records, "{}", json::stringify(result.record_json(record)))?;
+            records.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ResultObserver for ResultsWriter {
+    fn observe(&mut self, result: &GameResult) -> Result<(), HJError> {
+        self.write(result).map_err(|e| HJError::OutputError(format!("Cannot write results: {}", e)))
+    }
+}
+
+fn think_ms(record: &GameRecord) -> [f64; 4] {
+    record.think_time().map(|d| d.as_secs_f64() * 1000.)
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
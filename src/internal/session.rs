@@ -0,0 +1,161 @@
+use crate::api::supervisor::BotOptions;
+use crate::game::actor::Actor;
+use crate::game::data::{Card, Hand, Move, PassDirection};
+use crate::game::errors::HJError;
+use crate::game::game_info::GameInfo;
+use crate::game::validator::Validator;
+use crate::internal::actor_spec::create_actor;
+use crate::internal::hj_validator::HJValidator;
+use crate::util::deck::find_winner_pidx;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Passing,
+    Playing,
+    Finished,
+    // A bot failed partway through handing over cards or finishing a trick, so it is out of step with
+    // the game and nothing more can be played.
+    Failed,
+}
+
+// A game that is stepped from outside, one pass or card at a time, for the C and JavaScript interfaces.
+// Seats with a bot spec are played by that bot when the game is advanced; the other seats pass and play
+// through `pass` and `play`.
+pub struct GameSession {
+    game_info: GameInfo,
+    direction: PassDirection,
+    passes: [Option<Vec<Card>>; 4],
+    passed: bool,
+    failure: Option<String>,
+    bots: [Option<Box<dyn Actor>>; 4],
+}
+
+impl GameSession {
+    pub fn new(hands: [Hand; 4], direction: PassDirection, bot_specs: &[Option<String>; 4]) -> Result<Self, HJError> {
+        let mut bots: [Option<Box<dyn Actor>>; 4] = [None, None, None, None];
+        for (pidx, spec) in bot_specs.iter().enumerate() {
+            let Some(spec) = spec else { continue };
+            let mut bot = create_actor(spec, &BotOptions::default())?;
+            bot.initialize(pidx, hands[pidx].cards())?;
+            bots[pidx] = Some(bot);
+        }
+
+        // Seats without a bot have nothing to pass when no cards change hands.
+        let passes = [0, 1, 2, 3].map(|pidx| match (direction, &bots[pidx]) {
+            (PassDirection::None, None) => Some(vec![]),
+            _ => None,
+        });
+
+        let mut session = Self { game_info: GameInfo::new(hands), direction, passes, passed: false, failure: None, bots };
+        session.finish_pass()?;
+        Ok(session)
+    }
+
+    pub fn game_info(&self) -> &GameInfo { &self.game_info }
+
+    #[allow(dead_code)]
+    pub fn pass_direction(&self) -> PassDirection { self.direction }
+
+    pub fn has_bot(&self, pidx: usize) -> bool { self.bots[pidx].is_some() }
+
+    pub fn phase(&self) -> Phase {
+        if self.failure.is_some() { Phase::Failed }
+        else if self.game_info.result().is_some() { Phase::Finished }
+        else if self.passed { Phase::Playing }
+        else { Phase::Passing }
+    }
+
+    pub fn pass(&mut self, pidx: usize, cards: Vec<Card>) -> Result<(), HJError> {
+        if self.failure.is_some() { return Err(self.failure_error()); }
+        if self.passed { return Err(HJError::WrongPhaseError("The cards have already been passed.".to_owned())); }
+        if self.has_bot(pidx) { return Err(HJError::WrongPhaseError(format!("Seat {} is played by a bot.", pidx))); }
+
+        HJValidator::validate_pass(self.direction, &cards, pidx, &self.game_info)?;
+        self.passes[pidx] = Some(cards);
+        let result = self.finish_pass();
+        self.fail_on(result)
+    }
+
+    // Once every seat without a bot has passed, asks the bots for their cards and hands everything over.
+    fn finish_pass(&mut self) -> Result<(), HJError> {
+        if (0..4).any(|pidx| !self.has_bot(pidx) && self.passes[pidx].is_none()) { return Ok(()); }
+
+        let mut passes = self.passes.clone().map(Option::unwrap_or_default);
+        for (bot, pass) in self.bots.iter_mut().zip(passes.iter_mut()) {
+            if let Some(bot) = bot { *pass = bot.get_pass(self.direction)?; }
+        }
+        self.game_info.apply_pass::<HJValidator>(self.direction, &passes)?;
+        for (pidx, pass) in passes.iter().enumerate() {
+            if let Some(bot) = &mut self.bots[(pidx + self.direction.index_shift()) % 4] { bot.end_pass(pass)?; }
+        }
+
+        self.passed = true;
+        Ok(())
+    }
+
+    // Keeps the error of a bot that failed halfway through a step, after which the session only reports it.
+    fn fail_on<T>(&mut self, result: Result<T, HJError>) -> Result<T, HJError> {
+        if let Err(error) = &result { self.failure = Some(format!("{:?}", error)); }
+        result
+    }
+
+    fn failure_error(&self) -> HJError {
+        HJError::WrongPhaseError(format!("The game stopped when a bot failed: {}", self.failure.as_deref().unwrap_or_default()))
+    }
+
+    fn check_playing(&self) -> Result<(), HJError> {
+        match self.phase() {
+            Phase::Passing => Err(HJError::WrongPhaseError("Not every seat has passed yet.".to_owned())),
+            Phase::Finished => Err(HJError::WrongPhaseError("The game is over.".to_owned())),
+            Phase::Failed => Err(self.failure_error()),
+            Phase::Playing => Ok(()),
+        }
+    }
+
+    // Plays a card for the current seat, which must not have a bot. Illegal cards are rejected and leave the game unchanged.
+    pub fn play(&mut self, card: Card) -> Result<Move, HJError> {
+        self.check_playing()?;
+        let pidx = self.game_info.current_pidx();
+        // The bot would still hold the card and could try to play it later.
+        if self.has_bot(pidx) { return Err(HJError::WrongPhaseError(format!("Seat {} is played by a bot.", pidx))); }
+
+        self.apply_play(card)
+    }
+
+    fn apply_play(&mut self, card: Card) -> Result<Move, HJError> {
+        let new_move = Move(self.game_info.current_pidx(), card);
+        let mut trick = self.game_info.current_moves().clone();
+        self.game_info.apply_move::<HJValidator>(card)?;
+        trick.push(new_move);
+
+        let result = self.tell_bots(&trick);
+        self.fail_on(result)?;
+        Ok(new_move)
+    }
+
+    fn tell_bots(&mut self, trick: &Vec<Move>) -> Result<(), HJError> {
+        if trick.len() == 4 {
+            let winner_pidx = find_winner_pidx(trick);
+            for bot in self.bots.iter_mut().flatten() { bot.end_round(winner_pidx, trick)?; }
+        }
+        if let Some(result) = self.game_info.result() {
+            for bot in self.bots.iter_mut().flatten() { bot.end_game(result)?; }
+        }
+        Ok(())
+    }
+
+    // Lets the bot in the current seat play a card.
+    pub fn advance(&mut self) -> Result<Move, HJError> {
+        self.check_playing()?;
+
+        let pidx = self.game_info.current_pidx();
+        let current_moves = self.game_info.current_moves().clone();
+        let card = match &mut self.bots[pidx] {
+            Some(bot) => bot.play_card(&current_moves),
+            None => return Err(HJError::WrongPhaseError(format!("Seat {} is not played by a bot.", pidx))),
+        };
+        // A bot that cannot come up with a legal card will not do better when asked again.
+        let result = card.and_then(|card| self.apply_play(card));
+        self.fail_on(result)
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use crate::game::errors::HJError;
+use crate::internal::results::{GameResult, ResultObserver};
+
+#[derive(Clone, Debug, Default)]
+pub struct ActorStats {
+    games: usize,
+    // Games that ended because an actor failed; they count for nothing else.
+    failed_games: usize,
+    points: isize,
+    tricks_won: usize,
+    queen_of_spades_taken: usize,
+    jack_of_diamonds_captured: usize,
+    moon_shots: usize,
+    moon_blocks: usize,
+    hearts_broken: usize,
+    suits_voided_by_passing: usize,
+}
+
+#[derive(Default)]
+pub struct StatsCollector {
+    by_actor: BTreeMap<String, ActorStats>,
+    by_seat: [ActorStats; 4],
+}
+
+impl ActorStats {
+    fn add(&mut self, result: &GameResult, pidx: usize) {
+        let Some(record) = result.record() else {
+            self.failed_games += 1;
+            return;
+        };
+
+        self.games += 1;
+        self.points += record.scores()[pidx];
+        self.tricks_won += record.tricks().iter().filter(|t| t.winner_pidx() == pidx).count();
+        self.queen_of_spades_taken += (record.queen_of_spades_taker() == Some(pidx)) as usize;
+        self.jack_of_diamonds_captured += (record.jack_of_diamonds_taker() == Some(pidx)) as usize;
+        self.moon_shots += (record.moon_shooter() == Some(pidx)) as usize;
+        self.moon_blocks += (record.moon_blocker() == Some(pidx)) as usize;
+        self.hearts_broken += (record.hearts_breaker() == Some(pidx)) as usize;
+        self.suits_voided_by_passing += record.suits_voided_by_passing(pidx);
+    }
+
+    fn per_game(&self, count: usize) -> f64 {
+        if self.games == 0 { 0. } else { count as f64 / self.games as f64 }
+    }
+
+    fn columns(&self) -> [(&'static str, f64); 10] {
+        [
+            ("games", self.games as f64),
+            ("failed", self.failed_games as f64),
+            ("points", if self.games == 0 { 0. } else { self.points as f64 / self.games as f64 }),
+            ("tricks", self.per_game(self.tricks_won)),
+            ("q_spades", self.per_game(self.queen_of_spades_taken)),
+            ("j_diamonds", self.per_game(self.jack_of_diamonds_captured)),
+            ("moon_shot", self.per_game(self.moon_shots)),
+            ("moon_block", self.per_game(self.moon_blocks)),
+            ("hearts_broken", self.per_game(self.hearts_broken)),
+            ("voided_suits", self.per_game(self.suits_voided_by_passing)),
+        ]
+    }
+
+    fn json(&self) -> json::JsonValue {
+        json::object!{
+            games: self.games,
+            failed_games: self.failed_games,
+            points: self.points,
+            tricks_won: self.tricks_won,
+            queen_of_spades_taken: self.queen_of_spades_taken,
+            jack_of_diamonds_captured: self.jack_of_diamonds_captured,
+            moon_shots: self.moon_shots,
+            moon_blocks: self.moon_blocks,
+            hearts_broken: self.hearts_broken,
+            suits_voided_by_passing: self.suits_voided_by_passing,
+        }
+    }
+}
+
+impl StatsCollector {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn table(&self) -> String {
+        let mut rows: Vec<(String, &ActorStats)> = self.by_actor.iter().map(|(spec, stats)| (spec.clone(), stats)).collect();
+        rows.extend(self.by_seat.iter().enumerate().map(|(pidx, stats)| (format!("seat {}", pidx), stats)));
+
+        let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(5);
+        let mut table = format!("{:width$}", "", width = width);
+        for (name, _) in ActorStats::default().columns() { table.push_str(&format!(" {:>13}", name)); }
+        for (name, stats) in rows {
+            table.push_str(&format!("\n{:width$}", name, width = width));
+            for (_, value) in stats.columns() { table.push_str(&format!(" {:>13.3}", value)); }
+        }
+        table
+    }
+
+    pub fn json(&self) -> json::JsonValue {
+        let mut by_actor = json::JsonValue::new_object();
+        for (spec, stats) in &self.by_actor { by_actor[spec.as_str()] = stats.json(); }
+
+        json::object!{
+            by_actor: by_actor,
+            by_seat: self.by_seat.iter().map(|s| s.json()).collect::<Vec<json::JsonValue>>(),
+        }
+    }
+}
+
+impl ResultObserver for StatsCollector {
+    fn observe(&mut self, result: &GameResult) -> Result<(), HJError> {
+        for pidx in 0..4 {
+            self.by_actor.entry(result.actor_specs()[pidx].clone()).or_default().add(result, pidx);
+            self.by_seat[pidx].add(result, pidx);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+
+use iter_fixed::IntoIteratorFixed;
+
+use crate::api::supervisor::BotOptions;
+use crate::game::actor::Actor;
+use crate::game::errors::HJError;
+use crate::internal::actor_spec::create_actor;
+use crate::internal::game::play_recorded;
+use crate::internal::record::GameRecord;
+use crate::internal::results::{GameResult, ResultObserver};
+use crate::util::deals::Deal;
+use crate::util::platform;
+
+pub struct TournamentOptions {
+    pub deals: Vec<Deal>,
+    pub rotations: usize,
+    pub actor_specs: [String; 4],
+    pub bot_options: BotOptions,
+}
+
+enum TableEvent {
+    // The deal index, the rotation and how the game went.
+    Game(usize, usize, Box<Result<GameRecord, HJError>>),
+    // The actors could not be created, so the table plays nothing.
+    Closed(HJError),
+}
+
+// One rotation plays every deal with the same four actors, so a bot that fails a game is restarted
+// when the next one starts instead of being replaced.
+fn play_table(deals: Vec<Deal>, player_shift: usize, actor_specs: [String; 4], bot_options: BotOptions, events: mpsc::Sender<TableEvent>) {
+    let actors = actor_specs.iter().map(|s| create_actor(s, &bot_options)).collect::<Result<Vec<Box<dyn Actor>>, HJError>>();
+    let mut actors = match actors {
+        Ok(actors) => actors,
+        Err(error) => { let _ = events.send(TableEvent::Closed(error)); return },
+    };
+    let [a0, a1, a2, a3] = &mut actors[..] else { unreachable!() };
+
+    for (deal_idx, deal) in deals.iter().enumerate() {
+        let hands = [0, 1, 2, 3].into_iter_fixed().map(|i| deal.hands()[(player_shift + i) % 4].clone()).collect();
+        let outcome = play_recorded(hands, deal.pass_direction(), [a0.as_mut(), a1.as_mut(), a2.as_mut(), a3.as_mut()]);
+        // Nobody is listening any more once the tournament has been aborted.
+        if events.send(TableEvent::Game(deal_idx, player_shift, Box::new(outcome))).is_err() { return; }
+    }
+}
+
+// A failed game is passed to the observers like any other and leaves the scores alone; only actors
+// that cannot be created or results that cannot be written stop the tournament.
+pub fn run_tournament(options: &TournamentOptions, observers: &mut [&mut dyn ResultObserver]) -> Result<[isize; 4], HJError> {
+    let (sender, receiver) = mpsc::channel();
+    let tables: Vec<_> = (0..options.rotations).map(|player_shift| {
+        let deals = options.deals.clone();
+        let actor_specs = options.actor_specs.clone();
+        let bot_options = options.bot_options.clone();
+        let sender = sender.clone();
+        platform::spawn(move || play_table(deals, player_shift, actor_specs, bot_options, sender))
+    }).collect();
+    drop(sender);
+
+    let mut total_scores = [0, 0, 0, 0];
+    // Games finish out of order across tables, but are reported deal by deal and rotation by rotation.
+    let mut finished = HashMap::new();
+    let mut game_id = 0;
+
+    for event in receiver {
+        let (deal_idx, player_shift, outcome) = match event {
+            TableEvent::Game(deal_idx, player_shift, outcome) => (deal_idx, player_shift, outcome),
+            TableEvent::Closed(error) => return Err(error),
+        };
+        finished.insert(deal_idx * options.rotations + player_shift, *outcome);
+
+        while let Some(outcome) = finished.remove(&game_id) {
+            let deal = &options.deals[game_id / options.rotations];
+            let player_shift = game_id % options.rotations;
+            let deal_seats = [0, 1, 2, 3].map(|i| (player_shift + i) % 4);
+
+            if let Ok(record) = &outcome {
+                for (total, score) in total_scores.iter_mut().zip(record.scores()) { *total += score; }
+            }
+
+            let result = GameResult::new(game_id, deal.seed(), deal.id().to_owned(), deal.pass_direction(), deal_seats, options.actor_specs.clone(), outcome);
+            for observer in observers.iter_mut() { observer.observe(&result)?; }
+            game_id += 1;
+        }
+    }
+
+    for table in tables { table.join().expect("Tournament tables should not panic."); }
+
+    Ok(total_scores)
+}
@@ -19,6 +19,9 @@ def score(card):
     return 0
 
 def find_card():
+    if 'state' in message:
+        return random.choice(message['state']['legal_cards'])
+
     random.shuffle(cards)
     played_moves = message['played_moves']
     if len(played_moves):
@@ -49,7 +52,14 @@ while True:
         string += char
 
     message = json.loads(string)
-    if message['message'] == 'initialize':
+    if message['message'] == 'hello':
+        string = json.dumps({
+            'name': 'random_actor.py',
+            'author': 'hj',
+            'protocol_version': 1,
+            'features': ['state'],
+        })
+    elif message['message'] == 'initialize':
         pidx = message['pidx']
         cards = message['cards']
         string = '[]'
@@ -74,6 +84,7 @@ while True:
         string = json.dumps({'cards': pass_cards})
     elif message['message'] == 'end_pass':
         cards += message['passed_cards']
+        string = '[]'
     else:
         raise Error()
 
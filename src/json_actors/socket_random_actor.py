@@ -0,0 +1,37 @@
+#!/usr/bin/python3
+
+# Connects to `hj serve` and plays random legal cards until the server hangs up.
+# Usage: socket_random_actor.py tcp:HOST:PORT | unix:PATH
+
+import json
+import random
+import socket
+import sys
+
+
+address = sys.argv[1] if len(sys.argv) > 1 else 'tcp:localhost:4000'
+kind, _, rest = address.partition(':')
+if kind == 'unix':
+    connection = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    connection.connect(rest)
+else:
+    if kind != 'tcp':
+        rest = address
+    host, _, port = rest.rpartition(':')
+    connection = socket.create_connection((host, int(port)))
+
+stream = connection.makefile('rw')
+for line in stream:
+    message = json.loads(line)
+    if message['message'] == 'hello':
+        reply = {'name': 'socket_random_actor.py', 'author': 'hj', 'protocol_version': 1, 'features': ['state']}
+    elif message['message'] == 'play_card':
+        reply = {'card': random.choice(message['state']['legal_cards'])}
+    elif message['message'] == 'get_pass':
+        hand = message['state']['hand']
+        reply = {'cards': [] if message['direction'] == 'none' else random.sample(hand, 3)}
+    else:
+        reply = {}
+
+    stream.write(json.dumps(reply) + '\n')
+    stream.flush()
@@ -0,0 +1,51 @@
+// Everything public is re-exported below; the modules themselves are free to change.
+pub(crate) mod api;
+pub(crate) mod game;
+pub(crate) mod internal;
+pub(crate) mod rust_actors;
+pub(crate) mod util;
+
+pub use crate::api::rust::{play_match, ISMCTSV1, ISMCTSV1Builder, MCTSModV1, MCTSModV1Builder, MCTSV1, MCTSV1Builder, MatchBuilder, RandomActor, RuleV1};
+pub use crate::game::actor::Actor;
+pub use crate::game::data::{Card, Hand, Move, PassDirection, Rank, Suit};
+pub use crate::game::errors::HJError;
+pub use crate::game::game_info::{GameInfo, StopCondition};
+pub use crate::game::validator::{NoValidator, Validator};
+pub use crate::internal::hj_validator::HJValidator;
+pub use crate::internal::record::{GameRecord, Trick};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::rust_actors::actor_human::ActorHuman;
+pub use crate::rust_actors::util::get_allowed_cards;
+pub use crate::util::deals::Deal;
+pub use crate::api::supervisor::{BotOptions, EventHandler};
+
+// Plays deals with the same actors in every seat rotation, and hands each game to the observers.
+pub mod tournament {
+    pub use crate::internal::game::play_with_hands;
+    pub use crate::internal::record::load_records;
+    pub use crate::internal::results::{GameResult, ResultObserver, ResultsWriter};
+    pub use crate::internal::stats::StatsCollector;
+    pub use crate::internal::tournament::{run_tournament, TournamentOptions};
+}
+
+// Deal libraries: random and constrained deals, saved and loaded as JSON Lines.
+pub mod deals {
+    pub use crate::util::deal_constraints::DealConstraint;
+    pub use crate::util::deals::{generate_constrained_deals, generate_deals, load_deals, save_deals};
+}
+
+// Timings of the search building blocks, to compare against a saved baseline.
+pub mod bench {
+    pub use crate::internal::bench::{append_results, print_results, run_benchmarks, BenchResult};
+}
+
+// Seats played over the network: bots on a socket, people in a browser, and the analysis engine on stdin.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server {
+    pub use crate::api::engine::run_engine;
+    pub use crate::api::socket::SocketServer;
+    pub use crate::api::web::{WebServer, WEB_SPEC};
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::rust_actors::actor_human::ReplayViewer;
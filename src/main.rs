@@ -1,71 +1,282 @@
-mod api;
-mod game;
-mod internal;
-mod rust_actors;
-mod util;
-
-use std::thread;
-
-use iter_fixed::IntoIteratorFixed;
-use crate::api::json::JSONActor;
-
-use crate::rust_actors::actor_mcts_v1::ActorMCTSV1;
-use crate::rust_actors::actor_rule_v1::ActorRuleV1;
-use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
-use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
-use crate::game::data::{Card, PassDirection, Rank, Suit};
-use crate::rust_actors::actor_human::ActorHuman;
-use crate::rust_actors::actor_mcts_mod_v1::ActorMCTSModV1;
-use crate::rust_actors::actor_random::ActorRandom;
-use crate::rust_actors::determinize::determinize_v1::DeterminizeV1;
-use crate::rust_actors::eval_round::eval_round_v1::EvalRoundV1;
-use crate::rust_actors::eval_state::eval_state_v1::EvalStateV1;
-use crate::rust_actors::player_state::basic_player_state::BasicPlayerState;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hj::bench;
+use hj::deals::{self, DealConstraint};
+use hj::server::{run_engine, SocketServer, WebServer, WEB_SPEC};
+use hj::tournament::{load_records, play_with_hands, run_tournament, GameResult, ResultObserver, ResultsWriter, StatsCollector, TournamentOptions};
+use hj::{BotOptions, EventHandler, HJError, PassDirection, ReplayViewer};
+
+fn usage() -> ! {
+    eprintln!("Usage: hj [--games N] [--seed S] [--deals PATH] [--rotations R] [--csv PATH] [--jsonl PATH] [--records PATH] [--stats PATH] [--bot-timeout MS] [--bot-logs DIR] [SPEC SPEC SPEC SPEC]");
+    eprintln!("       hj deals [--count N] [--seed S] [--constraint C]... [--attempts N] [--pass DIR] --output PATH");
+    eprintln!("       hj serve ADDRESS [--games N] [--seed S] [--bot-timeout MS]");
+    eprintln!("       hj web ADDRESS [--games N] [--seed S] [SPEC SPEC SPEC SPEC]");
+    eprintln!("       hj replay PATH [--game N] [--seat P]");
+    eprintln!("       hj engine");
+    eprintln!("       hj bench [--seed S] [--time MS] [--label LABEL] [--output PATH] [--baseline PATH]");
+    std::process::exit(2)
+}
+
+// Prints every game as it is reported, with the running totals.
+struct Progress {
+    total_scores: [isize; 4],
+}
+
+impl ResultObserver for Progress {
+    fn observe(&mut self, result: &GameResult) -> Result<(), HJError> {
+        match (result.record(), result.error()) {
+            (Some(record), _) => {
+                for pidx in 0..4 { self.total_scores[pidx] += record.scores()[pidx]; }
+                println!("  {}: {:?}      (cumulative: {:?})", result.game_id(), record.scores(), self.total_scores);
+            },
+            (None, error) => println!("  {}: failed: {:?}", result.game_id(), error),
+        }
+        Ok(())
+    }
+}
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(|a| a.as_str()) {
+        Some("bench") => bench_main(args.into_iter().skip(1)),
+        Some("deals") => deals_main(args.into_iter().skip(1)),
+        Some("serve") => serve_main(args.into_iter().skip(1)),
+        Some("web") => web_main(args.into_iter().skip(1)),
+        Some("replay") => replay_main(args.into_iter().skip(1)),
+        Some("engine") if args.len() == 1 => run_engine(),
+        _ => tournament_main(args.into_iter()),
+    }
+}
+
+fn bench_main(mut args: impl Iterator<Item = String>) {
+    let mut seed = 0;
+    let mut time = 2000;
+    let mut label = "unlabelled".to_owned();
+    let mut output_path = None;
+    let mut baseline_path = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--seed" => seed = value().parse().unwrap_or_else(|_| usage()),
+            "--time" => time = value().parse().unwrap_or_else(|_| usage()),
+            "--label" => label = value(),
+            "--output" => output_path = Some(value()),
+            "--baseline" => baseline_path = Some(value()),
+            _ => usage(),
+        }
+    }
+
+    let results = bench::run_benchmarks(seed, Duration::from_millis(time));
+    bench::print_results(&results, baseline_path.as_deref()).expect("The baseline should be readable.");
+    if let Some(path) = output_path {
+        bench::append_results(&path, &label, seed, &results).expect("Benchmark results should be writable.");
+    }
+}
+
+fn deals_main(mut args: impl Iterator<Item = String>) {
+    let mut count = 100;
+    let mut seed = rand::random();
+    let mut output_path = None;
+    let mut constraints = vec![];
+    let mut attempts = 1_000_000;
+    let mut pass_direction = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--count" => count = value().parse().unwrap_or_else(|_| usage()),
+            "--constraint" => constraints.push(DealConstraint::parse(&value()).unwrap_or_else(|e| { eprintln!("{:?}", e); usage() })),
+            "--attempts" => attempts = value().parse().unwrap_or_else(|_| usage()),
+            "--pass" => pass_direction = Some(PassDirection::from_name(&value()).unwrap_or_else(|| usage())),
+            "--seed" => seed = value().parse().unwrap_or_else(|_| usage()),
+            "--output" => output_path = Some(value()),
+            _ => usage(),
+        }
+    }
+
+    let output_path = output_path.unwrap_or_else(|| usage());
+    let generated = if constraints.is_empty() {
+        deals::generate_deals(count, seed)
+    } else {
+        deals::generate_constrained_deals(count, seed, &constraints, attempts).unwrap_or_else(|e| { eprintln!("{:?}", e); std::process::exit(1) })
+    };
+    let generated: Vec<_> = match pass_direction {
+        Some(direction) => generated.into_iter().map(|d| d.with_pass_direction(direction)).collect(),
+        None => generated,
+    };
+    deals::save_deals(&output_path, &generated).expect("Deals should be writable.");
+    println!("Wrote {} deals with seed {} to {}", count, seed, output_path);
+}
+
+// Bot restarts, handshakes and seating are shown on stderr, next to the errors.
+fn print_events() -> Option<EventHandler> {
+    Some(Arc::new(|message| eprintln!("{}", message)))
+}
+
+fn serve_main(mut args: impl Iterator<Item = String>) {
+    let mut address = None;
+    let mut games = 1;
+    let mut seed = rand::random();
+    let mut timeout = Duration::from_secs(10);
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--games" => games = value().parse().unwrap_or_else(|_| usage()),
+            "--seed" => seed = value().parse().unwrap_or_else(|_| usage()),
+            "--bot-timeout" => timeout = Duration::from_millis(value().parse().unwrap_or_else(|_| usage())),
+            _ if arg.starts_with("--") || address.is_some() => usage(),
+            _ => address = Some(arg),
+        }
+    }
+
+    let fail = |error| -> ! { eprintln!("Server stopped: {:?}", error); std::process::exit(1) };
+    let options = BotOptions { timeout, on_event: print_events(), ..Default::default() };
+    let server = SocketServer::bind(&address.unwrap_or_else(|| usage()), options).unwrap_or_else(|e| fail(e));
+    println!("Waiting for four bots on {}", server.local_address());
+    let [mut a0, mut a1, mut a2, mut a3] = server.accept_seats().unwrap_or_else(|e| fail(e));
+
+    println!("Seed: {}", seed);
     let mut total_scores = [0, 0, 0, 0];
+    for (round_num, deal) in deals::generate_deals(games, seed).into_iter().enumerate() {
+        // A bot that drops out forfeits the game, and is replaced by the next bot to connect.
+        match play_with_hands(deal.hands().clone(), deal.pass_direction(), [&mut a0, &mut a1, &mut a2, &mut a3]) {
+            Ok(scores) => {
+                for pidx in 0..4 { total_scores[pidx] += scores[pidx]; }
+                println!("  {}: {:?}      (cumulative: {:?})", round_num, scores, total_scores);
+            },
+            Err(error) => println!("  {}: failed: {:?}", round_num, error),
+        }
+    }
+    println!("{:?}", total_scores);
+}
+
+fn web_main(mut args: impl Iterator<Item = String>) {
+    let mut address = None;
+    let mut games = 1;
+    let mut seed = rand::random();
+    let mut specs = vec![];
 
-    for round_num in (0..100).step_by(1) {
-        let base_hands = util::deck::get_shuffled_hands();
-
-        let handles: Vec<_> = (0..1).map(|player_shift| {
-            let hands = [0, 1, 2, 3].into_iter_fixed().map(|i| base_hands[(player_shift + i) % 4].clone()).collect();
-            thread::spawn(move || {
-                internal::game::play_with_hands(
-                    hands,
-                    PassDirection::from_round(round_num),
-                    [
-                        &mut ActorHuman::new(),
-                        &mut ActorRuleV1::new(),
-                        &mut ActorRandom::new(),
-                        &mut JSONActor::new("/home/arne/repos/hj/src/json_actors/random_actor.py".to_owned()),
-                    ],
-                ).expect("Errors should not occur.")
-            })
-        }).collect();
-
-        for handle in handles {
-            let scores = handle.join().unwrap();
-            total_scores[0] += scores[0];
-            total_scores[1] += scores[1];
-            total_scores[2] += scores[2];
-            total_scores[3] += scores[3];
-
-            println!("  {}: {:?}      (cumulative: {:?})", round_num, scores, total_scores);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--games" => games = value().parse().unwrap_or_else(|_| usage()),
+            "--seed" => seed = value().parse().unwrap_or_else(|_| usage()),
+            _ if arg.starts_with("--") => usage(),
+            _ if address.is_none() => address = Some(arg),
+            _ => specs.push(arg),
         }
+    }
+    let specs: [String; 4] = match specs.len() {
+        0 => [WEB_SPEC, "rule_v1", "rule_v1", "rule_v1"].map(|s| s.to_owned()),
+        _ => specs.try_into().unwrap_or_else(|_| usage()),
+    };
+
+    let fail = |error| -> ! { eprintln!("Server stopped: {:?}", error); std::process::exit(1) };
+    let options = BotOptions { on_event: print_events(), ..Default::default() };
+    let server = WebServer::bind(&address.unwrap_or_else(|| usage()), options).unwrap_or_else(|e| fail(e));
+    let mut actors = server.seat_actors(&specs).unwrap_or_else(|e| fail(e));
+    let [a0, a1, a2, a3] = &mut actors[..] else { unreachable!() };
 
-        // let scores = internal::game::play(PassDirection::from_round(round_num), [
-        //     &mut MCTSActorV1::<RuleActorV1>::new(50, 10),
-        //     &mut RuleActorV1::new(),
-        //     &mut RuleActorV1::new(),
-        //     &mut RuleActorV1::new(),
-        // ]).expect("Errors should not occur.");
-        // total_scores[0] += scores[0];
-        // total_scores[1] += scores[1];
-        // total_scores[2] += scores[2];
-        // total_scores[3] += scores[3];
-        //
-        // println!("  {}: {:?}      (cumulative: {:?})", round_num, scores, total_scores);
+    println!("Seed: {}", seed);
+    let mut total_scores = [0, 0, 0, 0];
+    for (round_num, deal) in deals::generate_deals(games, seed).into_iter().enumerate() {
+        let scores = play_with_hands(deal.hands().clone(), deal.pass_direction(), [a0.as_mut(), a1.as_mut(), a2.as_mut(), a3.as_mut()])
+            .unwrap_or_else(|e| fail(e));
+        for pidx in 0..4 { total_scores[pidx] += scores[pidx]; }
+        println!("  {}: {:?}      (cumulative: {:?})", round_num, scores, total_scores);
     }
     println!("{:?}", total_scores);
 }
+
+fn replay_main(mut args: impl Iterator<Item = String>) {
+    let mut path = None;
+    let mut game = 0;
+    let mut seat = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--game" => game = value().parse().unwrap_or_else(|_| usage()),
+            "--seat" => seat = Some(value().parse::<usize>().ok().filter(|s| *s < 4).unwrap_or_else(|| usage())),
+            _ if arg.starts_with("--") || path.is_some() => usage(),
+            _ => path = Some(arg),
+        }
+    }
+
+    let fail = |error| -> ! { eprintln!("Cannot replay the game: {:?}", error); std::process::exit(1) };
+    let mut records = load_records(&path.unwrap_or_else(|| usage())).unwrap_or_else(|e| fail(e));
+    if game >= records.len() {
+        eprintln!("The file has {} games; --game counts from 0.", records.len());
+        std::process::exit(1);
+    }
+    let viewer = ReplayViewer::new(records.swap_remove(game)).unwrap_or_else(|e| fail(e));
+    viewer.run(seat).unwrap_or_else(|e| fail(e));
+}
+
+fn tournament_main(mut args: impl Iterator<Item = String>) {
+    let mut options = TournamentOptions {
+        deals: vec![],
+        rotations: 1,
+        actor_specs: ["mcts_v1:50:10", "rule_v1", "rule_v1", "rule_v1"].map(|s| s.to_owned()),
+        bot_options: BotOptions { on_event: print_events(), ..Default::default() },
+    };
+    let mut games = None;
+    let mut seed = rand::random();
+    let mut deals_path = None;
+    let mut csv_path = None;
+    let mut jsonl_path = None;
+    let mut records_path = None;
+    let mut stats_path = None;
+    let mut specs = vec![];
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match arg.as_str() {
+            "--games" => games = Some(value().parse().unwrap_or_else(|_| usage())),
+            "--seed" => seed = value().parse().unwrap_or_else(|_| usage()),
+            "--deals" => deals_path = Some(value()),
+            "--rotations" => options.rotations = value().parse().unwrap_or_else(|_| usage()),
+            "--csv" => csv_path = Some(value()),
+            "--jsonl" => jsonl_path = Some(value()),
+            "--records" => records_path = Some(value()),
+            "--stats" => stats_path = Some(value()),
+            "--bot-timeout" => options.bot_options.timeout = Duration::from_millis(value().parse().unwrap_or_else(|_| usage())),
+            "--bot-logs" => options.bot_options.log_dir = Some(value()),
+            _ if arg.starts_with("--") => usage(),
+            _ => specs.push(arg),
+        }
+    }
+    match specs.len() {
+        0 => {}
+        4 => options.actor_specs = specs.try_into().unwrap(),
+        _ => usage(),
+    }
+    if !(1..=4).contains(&options.rotations) { usage(); }
+
+    // With a deal library, `--games` plays only its first deals.
+    options.deals = match deals_path {
+        Some(path) => {
+            let mut library = deals::load_deals(&path).expect("The deal library should be valid.");
+            library.truncate(games.unwrap_or(library.len()));
+            library
+        },
+        None => { println!("Seed: {}", seed); deals::generate_deals(games.unwrap_or(100), seed) },
+    };
+
+    let mut writer = ResultsWriter::new(csv_path.as_deref(), jsonl_path.as_deref(), records_path.as_deref()).expect("Results files should be writable.");
+    let mut stats = StatsCollector::new();
+    let mut progress = Progress { total_scores: [0; 4] };
+    let total_scores = run_tournament(&options, &mut [&mut progress, &mut writer, &mut stats]).unwrap_or_else(|error| {
+        eprintln!("Tournament aborted: {:?}", error);
+        std::process::exit(1)
+    });
+    println!("{:?}", total_scores);
+
+    println!("\n{}", stats.table());
+    if let Some(path) = stats_path {
+        std::fs::write(path, json::stringify_pretty(stats.json(), 2)).expect("Statistics should be writable.");
+    }
+}
@@ -1,5 +1,7 @@
 pub mod actor_dummy;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod actor_human;
+pub mod actor_ismcts_v1;
 pub mod actor_mcts_mod_v1;
 pub mod actor_mcts_v1;
 pub mod actor_random;
@@ -9,4 +11,4 @@ pub mod determinize;
 pub mod eval_round;
 pub mod eval_state;
 pub mod player_state;
-mod util;
+pub mod util;
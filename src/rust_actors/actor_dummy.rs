@@ -1,5 +1,6 @@
 use crate::{Card, PassDirection};
 use crate::game::actor::Actor;
+use crate::game::errors::HJError;
 use crate::game::data::Move;
 use crate::rust_actors::player_state::BasicPlayerStateInterface;
 
@@ -20,34 +21,34 @@ impl<PlayerState: BasicPlayerStateInterface> ActorDummy<PlayerState> {
 }
 
 impl<PlayerState: BasicPlayerStateInterface> Actor for ActorDummy<PlayerState> {
-    fn initialize(&mut self, _pidx: usize, _cards: &Vec<Card>) {
+    fn initialize(&mut self, _pidx: usize, _cards: &Vec<Card>) -> Result<(), HJError> {
         panic!("Dummy actors should never receive #initialize");
     }
 
-    fn play_card(&mut self, played_moves: &Vec<Move>) -> Card {
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
         if let Some(card) = self.next_card {
             self.player_state.update_play_card(played_moves);
             self.player_state.update_did_play_card(&card);
             self.next_card = None;
-            card
+            Ok(card)
         } else {
             panic!("This dummy actor has no card left to play")
         }
     }
 
-    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) {
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
         self.player_state.update_end_round(played_moves, winner_pidx);
+        Ok(())
     }
 
-    fn end_game(&mut self, _score: [isize; 4]) {
-        todo!()
-    }
+    // Reached when a search expands the last card of the game.
+    fn end_game(&mut self, _score: [isize; 4]) -> Result<(), HJError> { Ok(()) }
 
-    fn get_pass(&mut self, _direction: PassDirection) -> Vec<Card> {
+    fn get_pass(&mut self, _direction: PassDirection) -> Result<Vec<Card>, HJError> {
         todo!()
     }
 
-    fn end_pass(&mut self, _passed_cards: &Vec<Card>) {
+    fn end_pass(&mut self, _passed_cards: &Vec<Card>) -> Result<(), HJError> {
         todo!()
     }
 }
@@ -1,127 +1,222 @@
-use core::default::Default;
+use std::sync::Arc;
+
 use crate::game::actor::Actor;
+use crate::game::errors::HJError;
 use crate::game::data::{Card, Move, PassDirection};
+use crate::rust_actors::actor_human::advisor::Advisor;
+use crate::rust_actors::actor_human::screen::{Input, Screen, SharedScreen};
+use crate::rust_actors::actor_human::table::{card_name, Prompt, Table};
+use crate::rust_actors::util::get_allowed_cards;
 
-use std::io::Read;
+mod advisor;
+mod history;
+mod replay;
+mod screen;
+mod table;
 
-use colored::Colorize;
+pub use replay::ReplayViewer;
 
+// Lets a person play in a full-screen terminal view of the table, with engine hints on request.
+// Several human seats take turns at the same terminal, handing over the keyboard in between.
 pub struct ActorHuman {
-    cards: Vec<Card>,
-    pidx: usize,
-    score: [isize; 4],
+    table: Table,
+    screen: Option<SharedScreen>,
+    advisor: Advisor,
+    // Whether to point out likely mistakes after each trick.
+    kibitz: bool,
+    commentary: Option<String>,
 }
 
 
 impl ActorHuman {
     #[allow(dead_code)]
     pub fn new() -> Self {
-        Self { cards: vec![], pidx: 0, score: [0; 4] }
+        Self { table: Table::new(), screen: None, advisor: Advisor::new(0, &[]), kibitz: false, commentary: None }
     }
 
-    fn show_moves(&self, moves: &Vec<Move>, winner_pidx: Option<usize>) {
-        println!("{}", "\n".repeat(10));
-        println!("Player 0 ({:3}){}Player 1 ({:3}){}Player 2 ({:3}){}Player 3 ({:3})", self.score[0], " ".repeat(11), self.score[1], " ".repeat(11), self.score[2], " ".repeat(11), self.score[3]);
+    pub fn with_kibitzer() -> Self {
+        Self { kibitz: true, ..Self::new() }
+    }
 
-        let mut ordered_cards = [None; 4];
-        let mut card_string: String = Default::default();
-        for mv in moves {
-            ordered_cards[mv.pidx()] = Some(mv.card());
-        }
-        for pidx in 0..4 {
-            let new_string = format!(
-                "{:25}",
-                match ordered_cards[pidx] {
-                    None => "".to_owned(),
-                    Some(card) => format!("{:?}", card),
-                }
-            );
-            if Some(pidx) == winner_pidx { card_string.push_str(&new_string.bold()) }
-            else { card_string.push_str(&new_string); }
+    // Shows the table until the player picks `count` of the `allowed` cards, or until any key when `count` is zero.
+    fn ask(&mut self, message: String, count: usize, allowed: Vec<Card>) -> Result<Vec<Card>, HJError> {
+        if self.screen.is_none() { self.screen = Some(Screen::shared()?); }
+        let shared = self.screen.as_ref().unwrap();
+        let mut screen = shared.lock().expect("The shared screen should not be poisoned.");
+
+        if screen.holder != Some(self.table.pidx) {
+            // News for a seat away from the keyboard waits until that player is back; decisions call them over.
+            if count == 0 && screen.holder.is_some() { return Ok(vec![]); }
+            if Arc::strong_count(shared) > 1 { Self::hand_over(&self.table, &mut screen)?; }
+            screen.holder = Some(self.table.pidx);
         }
 
-        println!("{}", card_string);
-    }
+        let hand = self.table.sorted_hand();
+        let mut prompt = Prompt::new(message, count, allowed);
+        prompt.notice = self.commentary.take();
+        prompt.cursor = hand.iter().position(|c| prompt.allowed.contains(c)).unwrap_or(0);
+        loop {
+            let card_areas = screen.draw(|frame| self.table.render(frame, &prompt))?;
+            let input = screen.next_input()?;
+
+            if let Input::Quit = input { return Err(HJError::ActorError("The player left the game.".to_owned())); }
+            if let Input::History = input { prompt.history = !prompt.history; continue; }
+            if count == 0 {
+                if let Input::Other | Input::Confirm | Input::Toggle | Input::Back | Input::Click(..) = input { return Ok(vec![]); }
+                continue;
+            }
 
-    fn query_card(&self) -> Card {
-        let mut cards_by_suit = [vec![], vec![], vec![], vec![]];
-        for card in &self.cards { cards_by_suit[card.suit() as usize - 1].push(card); }
-        for sidx in 0..4 { cards_by_suit[sidx].sort(); }
-
-        let mut card_string: String = "\n\n\n".to_owned();
-        let mut counter = 1;
-        let mut choices = vec![];
-        for cidx in 0.. {
-            let mut found_one = false;
-            for sidx in 0..4 {
-                match cards_by_suit[sidx].get(cidx) {
-                    None => card_string.push_str(&" ".repeat(25)),
-                    Some(card) => {
-                        card_string.push_str(&format!("{:4}{:21}", format!("{}.", counter), format!("{:?}", card)));
-                        choices.push(card);
-                        found_one = true;
-                        counter += 1;
-                    },
+            // Passes are shown once more as a set before they are handed over.
+            if prompt.confirming {
+                match input {
+                    Input::Confirm => return Ok(prompt.chosen),
+                    Input::Quit | Input::Other => continue,
+                    _ => { prompt.confirming = false; prompt.notice = None; },
                 }
+                if let Input::Back = input { continue; }
             }
-            card_string.push_str(&"\n");
 
-            if !found_one { break; }
+            prompt.notice = None;
+            let picked = match input {
+                Input::Hint => {
+                    prompt.notice = Some("The engine is thinking…".to_owned());
+                    screen.draw(|frame| self.table.render(frame, &prompt))?;
+                    let trick = &self.table.trick;
+                    prompt.hint = if count == 1 { self.advisor.play_hint(trick) } else { self.advisor.pass_hint() };
+                    prompt.notice = None;
+                    None
+                },
+                Input::Left => { prompt.cursor = prompt.cursor.saturating_sub(1); None },
+                Input::Right => { prompt.cursor = (prompt.cursor + 1).min(hand.len() - 1); None },
+                Input::Up | Input::Down => { prompt.cursor = Self::other_suit(&hand, prompt.cursor, matches!(input, Input::Down)); None },
+                Input::Toggle => Some(hand[prompt.cursor]),
+                Input::Confirm if count == 1 => Some(hand[prompt.cursor]),
+                Input::Confirm if prompt.chosen.len() == count => {
+                    let names: Vec<String> = prompt.chosen.iter().map(|c| card_name(*c)).collect();
+                    prompt.notice = Some(format!("Pass {}? Enter to confirm, Backspace to change them.", names.join(", ")));
+                    prompt.confirming = true;
+                    None
+                },
+                Input::Confirm => { prompt.notice = Some(format!("Pick {} more.", count - prompt.chosen.len())); None },
+                Input::Back => { prompt.chosen.pop(); None },
+                Input::Click(x, y) => card_areas.iter().find(|(area, _)| area.contains((x, y).into())).map(|(_, card)| *card),
+                Input::Other => { prompt.notice = Some("Use the arrow keys or the mouse to choose, and Enter to confirm.".to_owned()); None },
+                Input::Quit | Input::History => None,
+            };
+
+            let Some(card) = picked else { continue };
+            prompt.cursor = hand.iter().position(|c| *c == card).unwrap_or(prompt.cursor);
+            if !prompt.allowed.contains(&card) {
+                prompt.notice = Some(format!("You cannot play {}: {}", card_name(card), self.table.forbidding_rule(card)));
+                continue;
+            }
+            if count == 1 { return Ok(vec![card]); }
+            if prompt.chosen.contains(&card) { prompt.chosen.retain(|c| *c != card); }
+            else if prompt.chosen.len() < count { prompt.chosen.push(card); }
+            else { prompt.notice = Some(format!("You already picked {} cards; unpick one first.", count)); }
         }
+    }
 
-        println!("{}", card_string);
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
-        **choices[input.trim().parse::<usize>().unwrap() - 1]
+    // Hides the previous player's hand until the next one has the keyboard.
+    fn hand_over(table: &Table, screen: &mut Screen) -> Result<(), HJError> {
+        let mut prompt = Prompt::new(format!("Pass the keyboard to player {}, then press Enter.", table.pidx), 0, vec![]);
+        prompt.hidden = true;
+        loop {
+            screen.draw(|frame| table.render(frame, &prompt))?;
+            match screen.next_input()? {
+                Input::Quit => return Err(HJError::ActorError("The player left the game.".to_owned())),
+                Input::Confirm => return Ok(()),
+                _ => continue,
+            }
+        }
     }
 
-    pub fn pause() {
-        std::io::stdin().read(&mut [0]).unwrap();
+    // Moves the cursor to the closest card of the previous or next suit in the hand.
+    fn other_suit(hand: &[Card], cursor: usize, down: bool) -> usize {
+        let suit = hand[cursor].suit();
+        let column = hand.iter().take(cursor).filter(|c| c.suit() == suit).count();
+        let target = if down {
+            hand.iter().map(|c| c.suit()).find(|s| *s > suit)
+        } else {
+            hand.iter().map(|c| c.suit()).rev().find(|s| *s < suit)
+        };
+
+        match target {
+            Some(target) => {
+                let start = hand.iter().position(|c| c.suit() == target).unwrap();
+                let length = hand.iter().filter(|c| c.suit() == target).count();
+                start + column.min(length - 1)
+            },
+            None => cursor,
+        }
     }
 }
 
 impl Actor for ActorHuman {
-    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) {
-        self.pidx = pidx;
-        self.cards = cards.clone();
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError> {
+        self.table = Table::new();
+        self.table.pidx = pidx;
+        self.table.hand = cards.clone();
+        self.advisor = Advisor::new(pidx, cards);
+        // Taken now, so every human seat knows from the first prompt whether others share the terminal.
+        self.screen = Some(Screen::shared()?);
+        Ok(())
     }
 
-    fn play_card(&mut self, played_moves: &Vec<Move>) -> Card {
-        self.show_moves(played_moves, None);
-        let card = self.query_card();
-        self.cards.retain(|c| *c != card);
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
+        self.table.trick = played_moves.clone();
+        let allowed = get_allowed_cards(&self.table.game_info());
+        let card = self.ask("Your turn: choose a card to play.".to_owned(), 1, allowed)?[0];
+        self.advisor.did_play(played_moves, card);
+        self.table.hand.retain(|c| *c != card);
 
-        card
+        Ok(card)
     }
 
-    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) {
-        self.score[winner_pidx] += played_moves.iter().map(|m| m.card().score()).sum::<isize>();
-        self.show_moves(played_moves, Some(winner_pidx));
-
-        ActorHuman::pause();
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
+        self.table.points[winner_pidx] += played_moves.iter().map(|m| m.card().score()).sum::<isize>();
+        self.table.tricks_won[winner_pidx] += 1;
+        self.table.played.extend(played_moves.iter().map(|m| m.card()));
+        self.table.trick = vec![];
+        self.table.tricks.push((played_moves.clone(), winner_pidx));
+        let remark = self.advisor.end_round(played_moves, winner_pidx);
+        if self.kibitz { self.commentary = remark; }
+
+        let winner_card = played_moves.iter().find(|m| m.pidx() == winner_pidx).map_or("?".to_owned(), |m| card_name(m.card()));
+        self.ask(format!("Player {} takes the trick with {}.", winner_pidx, winner_card), 0, vec![])?;
+        Ok(())
     }
 
-    fn end_game(&mut self, _scores: [isize; 4]) {}
+    fn end_game(&mut self, scores: [isize; 4]) -> Result<(), HJError> {
+        self.table.points = scores;
+        self.ask(format!("The game is over. Final scores: {:?}.", scores), 0, vec![])?;
+        self.screen = None;
+        Ok(())
+    }
 
-    fn get_pass(&mut self, direction: PassDirection) -> Vec<Card> {
-        match direction {
-            PassDirection::None => { println!("No-pass round"); ActorHuman::pause(); vec![] }
-            _ => {
-                println!("Pass direction: {:?}", direction);
-                ActorHuman::pause();
-                (0..3).map(|_| { let card = self.query_card(); self.cards.retain(|c| *c != card); card }).collect()
-            }
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError> {
+        self.table.direction = Some(direction);
+        if direction == PassDirection::None {
+            self.ask("No cards are passed this round.".to_owned(), 0, vec![])?;
+            return Ok(vec![]);
         }
+
+        let to_pidx = (self.table.pidx + direction.index_shift()) % 4;
+        let cards = self.ask(format!("Choose three cards to pass to player {}.", to_pidx), 3, self.table.hand.clone())?;
+        self.table.hand.retain(|c| !cards.contains(c));
+        self.table.passed = cards.clone();
+        self.advisor.did_pass(&cards, direction);
+        Ok(cards)
     }
 
-    fn end_pass(&mut self, passed_cards: &Vec<Card>) {
-        self.cards.append(&mut passed_cards.clone());
-        if passed_cards.len() > 0 {
-            println!("Cards received:");
-            for card in passed_cards {
-                println!("  {:?}", card);
-            }
-            ActorHuman::pause();
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError> {
+        self.table.hand.extend(passed_cards.iter().copied());
+        self.table.received = passed_cards.clone();
+        self.advisor.end_pass(passed_cards);
+        if !passed_cards.is_empty() {
+            let names: Vec<String> = passed_cards.iter().map(|c| card_name(*c)).collect();
+            self.ask(format!("You received {}.", names.join(", ")), 0, vec![])?;
         }
+        Ok(())
     }
 }
@@ -0,0 +1,103 @@
+use crate::rust_actors::actor_rule_v1::ActorRuleV1;
+use crate::rust_actors::eval_round::eval_round_v1::EvalRoundV1;
+use crate::rust_actors::eval_state::eval_state_v1::EvalStateV1;
+use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
+use crate::game::data::{Card, Move, PassDirection};
+use crate::rust_actors::actor_mcts_v1::ActorMCTSV1;
+use crate::rust_actors::actor_human::table::card_name;
+use crate::rust_actors::player_state::BasicPlayerStateInterface;
+
+// How long the engine may think about a hint: `HINT_TRIES` searches of `HINT_TIMEOUT` ms each.
+const HINT_TIMEOUT: usize = 100;
+const HINT_TRIES: usize = 5;
+// How much more a card may cost than the engine's choice before the kibitzer speaks up.
+const MISTAKE_COST: f32 = 1.;
+
+// The engine's view from the human's seat, kept in step with the game like a bot's own player state.
+pub struct Advisor {
+    player_state: ExtendedPlayerState,
+    // The card the human played this trick, its cost, and the cheapest card with its cost.
+    last_play: Option<(Card, f32, Card, f32)>,
+}
+
+impl Advisor {
+    pub fn new(pidx: usize, cards: &[Card]) -> Self {
+        let mut player_state = ExtendedPlayerState::default();
+        player_state.set_pidx(pidx);
+        player_state.set_cards(cards.to_vec());
+        Self { player_state, last_play: None }
+    }
+
+    fn rule_actor(&self, played_moves: &Vec<Move>) -> ActorRuleV1<EvalRoundV1, EvalStateV1, ExtendedPlayerState> {
+        let mut actor = ActorRuleV1::new();
+        actor.player_state = self.player_state.clone();
+        actor.player_state.update_play_card(played_moves);
+        actor
+    }
+
+    // What the engine would play and why, best first: MCTS values with the rule-based costs beside them.
+    // Only the six best cards are listed, to fit beside the table.
+    pub fn play_hint(&self, played_moves: &Vec<Move>) -> Vec<String> {
+        let rule = self.rule_actor(played_moves);
+        let costs = rule.card_costs(played_moves);
+
+        let mut mcts = ActorMCTSV1::new(HINT_TIMEOUT, HINT_TRIES);
+        mcts.set_player_state(rule.player_state.clone());
+        let mut values: Vec<(Card, f32, usize)> = mcts.card_values(played_moves).into_iter()
+            .map(|(card, (value, visits))| (card, if visits == 0 { 0. } else { value / visits as f32 }, visits))
+            .collect();
+        values.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let Some((best, _, _)) = values.first() else { return vec!["The engine sees nothing to choose.".to_owned()] };
+        let cheapest = costs.iter().min_by_key(|(_, cost)| *cost).map(|(card, _)| *card);
+        let mut lines = vec![format!("The engine would play {}.", card_name(*best))];
+        if cheapest != Some(*best) {
+            lines.push(format!("By cost alone: {}.", cheapest.map_or("?".to_owned(), card_name)));
+        }
+        lines.push("Card  value  games  cost".to_owned());
+        for (card, value, visits) in values.into_iter().take(6) {
+            let cost = costs.iter().find(|(c, _)| *c == card).map_or(0., |(_, cost)| cost.value());
+            lines.push(format!("{:<5} {:>5.2} {:>6} {:>5.1}", card_name(card), value, visits, cost));
+        }
+        lines
+    }
+
+    pub fn pass_hint(&self) -> Vec<String> {
+        let mut rule = ActorRuleV1::new();
+        rule.player_state = self.player_state.clone();
+        let cards: Vec<String> = rule.choose_three_to_pass().into_iter().map(card_name).collect();
+        vec![
+            format!("The engine would pass {}.", cards.join(", ")),
+            "It leaves the cheapest hand.".to_owned(),
+        ]
+    }
+
+    pub fn did_pass(&mut self, cards: &[Card], direction: PassDirection) {
+        self.player_state.update_did_pass(cards, direction);
+    }
+
+    pub fn end_pass(&mut self, passed_cards: &Vec<Card>) {
+        self.player_state.update_end_pass(passed_cards);
+    }
+
+    pub fn did_play(&mut self, played_moves: &Vec<Move>, card: Card) {
+        let costs = self.rule_actor(played_moves).card_costs(played_moves);
+        let cost_of = |card: Card| costs.iter().find(|(c, _)| *c == card).map_or(0., |(_, cost)| cost.value());
+        self.last_play = costs.iter().min_by_key(|(_, cost)| *cost).map(|(best, _)| (card, cost_of(card), *best, cost_of(*best)));
+
+        self.player_state.update_play_card(played_moves);
+        self.player_state.update_did_play_card(&card);
+    }
+
+    // A remark on the human's card in the finished trick, when it looks like a mistake.
+    pub fn end_round(&mut self, played_moves: &Vec<Move>, winner_pidx: usize) -> Option<String> {
+        self.player_state.update_end_round(played_moves, winner_pidx);
+
+        let (card, cost, best, best_cost) = self.last_play.take()?;
+        if card == best || cost - best_cost < MISTAKE_COST { return None; }
+        Some(format!(
+            "Kibitzer: {} looked about {:.1} points worse than {}.",
+            card_name(card), cost - best_cost, card_name(best),
+        ))
+    }
+}
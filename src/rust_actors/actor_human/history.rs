@@ -0,0 +1,96 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::game::data::{Card, Move, Suit};
+use crate::rust_actors::actor_human::table::{card_name, card_style, Table};
+
+impl Table {
+    // The finished tricks and the current one, plus what they tell about the cards still out.
+    pub fn render_history(&self, frame: &mut Frame, area: Rect, side: Rect) {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut header = vec![Span::styled("  #  ", bold)];
+        header.extend((0..4).map(|pidx| Span::styled(format!("{:<6}", if pidx == self.pidx { "You".to_owned() } else { format!("P{}", pidx) }), bold)));
+        header.push(Span::styled("Won  Pts", bold));
+
+        let mut lines = vec![Line::from(header)];
+        let current = (!self.trick.is_empty()).then(|| (self.trick.clone(), None));
+        let tricks = self.tricks.iter().map(|(moves, winner)| (moves.clone(), Some(*winner))).chain(current);
+        for (number, (moves, winner)) in tricks.enumerate() {
+            let mut spans = vec![Span::raw(format!("{:>3}  ", number + 1))];
+            for pidx in 0..4 {
+                spans.push(match moves.iter().find(|m| m.pidx() == pidx) {
+                    Some(m) => {
+                        let mut style = card_style(m.card());
+                        if moves.first().map(|m| m.pidx()) == Some(pidx) { style = style.add_modifier(Modifier::ITALIC); }
+                        if winner == Some(pidx) { style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED); }
+                        Span::styled(format!("{:<6}", card_name(m.card())), style)
+                    },
+                    None => Span::raw(format!("{:<6}", "·")),
+                });
+            }
+            let points = moves.iter().map(|m| m.card().score()).sum::<isize>();
+            spans.push(Span::raw(match winner {
+                Some(winner) => format!("P{:<3} {:>3}", winner, points),
+                None => "…".to_owned(),
+            }));
+            lines.push(Line::from(spans));
+        }
+        if lines.len() == 1 { lines.push(Line::from("No cards have been played yet.")); }
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title("Tricks · Tab to go back")), area);
+
+        frame.render_widget(Paragraph::new(self.summary_lines()).wrap(Wrap { trim: false }).block(Block::bordered()), side);
+    }
+
+    fn summary_lines(&self) -> Vec<Line<'static>> {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut lines = vec![Line::from(Span::styled("Points taken", bold))];
+        for pidx in 0..4 {
+            let taken: Vec<Card> = self.tricks.iter()
+                .filter(|(_, winner)| *winner == pidx)
+                .flat_map(|(moves, _)| moves.iter().map(|m| m.card()))
+                .filter(|c| c.score() != 0)
+                .collect();
+            let mut spans = vec![Span::raw(format!("P{} {:>3}  ", pidx, self.points[pidx]))];
+            spans.extend(taken.iter().map(|c| Span::styled(format!("{} ", card_name(*c)), card_style(*c))));
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Shown void in", bold)));
+        for pidx in (0..4).filter(|pidx| *pidx != self.pidx) {
+            let voids: Vec<String> = self.voids(pidx).iter().map(|s| format!("{:?}", s)).collect();
+            lines.push(Line::from(format!("P{}  {}", pidx, if voids.is_empty() { "-".to_owned() } else { voids.join(" ") })));
+        }
+
+        // Scoring cards nobody has played yet, split into the player's own and those still with the others.
+        let seen: Vec<Card> = self.played.iter().copied().chain(self.trick.iter().map(|m| m.card())).collect();
+        let mut scoring: Vec<Card> = Card::all().into_iter().filter(|c| c.score() != 0 && !seen.contains(c)).collect();
+        scoring.sort_by_key(|c| (c.suit(), c.rank()));
+        let (yours, out): (Vec<Card>, Vec<Card>) = scoring.into_iter().partition(|c| self.hand.contains(c));
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Scoring cards still out", bold)));
+        for (label, cards) in [("Others: ", out), ("Yours:  ", yours)] {
+            let mut spans = vec![Span::raw(label)];
+            spans.extend(cards.iter().map(|c| Span::styled(format!("{} ", card_name(*c)), card_style(*c))));
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+
+    // The suits `pidx` did not follow when they were led.
+    fn voids(&self, pidx: usize) -> Vec<Suit> {
+        let tricks = self.tricks.iter().map(|(moves, _)| moves.as_slice()).chain([self.trick.as_slice()]);
+        let mut voids: Vec<Suit> = tricks.filter_map(|moves| {
+            let Move(_, led) = moves.first()?;
+            let Move(_, card) = moves.iter().find(|m| m.pidx() == pidx)?;
+            (card.suit() != led.suit()).then_some(led.suit())
+        }).collect();
+        voids.sort();
+        voids.dedup();
+        voids
+    }
+}
@@ -0,0 +1,131 @@
+use crate::game::data::{Card, Move, PassDirection};
+use crate::game::errors::HJError;
+use crate::game::game_info::GameInfo;
+use crate::internal::hj_validator::HJValidator;
+use crate::internal::record::GameRecord;
+use crate::rust_actors::actor_human::screen::{Input, Screen};
+use crate::rust_actors::actor_human::table::{card_name, Prompt, Table};
+
+const HELP: &str = "←→ card by card · ↑↓ trick by trick · Space for all hands · Enter for the next seat · Tab for the history · q to quit";
+
+// One position in the game: the state after the deal, after the pass, or after each card.
+struct Step {
+    game_info: GameInfo,
+    passed: bool,
+    description: String,
+}
+
+// Steps through a recorded game in the terminal, showing all four hands or what one seat could see.
+pub struct ReplayViewer {
+    record: GameRecord,
+    steps: Vec<Step>,
+}
+
+impl ReplayViewer {
+    // Replays the record through the rules once up front, so a broken record fails before anything is shown.
+    pub fn new(record: GameRecord) -> Result<Self, HJError> {
+        let direction = record.pass_direction();
+        let mut game_info = GameInfo::new(record.hands().clone());
+        let mut steps = vec![Step {
+            game_info: game_info.clone(),
+            passed: false,
+            description: match direction {
+                PassDirection::None => "The cards are dealt; no cards are passed this round.".to_owned(),
+                direction => format!("The cards are dealt; three cards are passed {}.", direction.name()),
+            },
+        }];
+
+        if direction != PassDirection::None {
+            game_info.apply_pass::<HJValidator>(direction, record.passes())?;
+            steps.push(Step { game_info: game_info.clone(), passed: true, description: format!("The cards are passed {}.", direction.name()) });
+        }
+
+        for (round, trick) in record.tricks().iter().enumerate() {
+            for (position, m) in trick.moves().iter().enumerate() {
+                if m.pidx() != game_info.current_pidx() {
+                    return Err(HJError::InvalidDealError(format!("Player {} played out of turn in trick {}", m.pidx(), round + 1)));
+                }
+                game_info.apply_move::<HJValidator>(m.card())?;
+
+                let mut description = format!("Trick {}: player {} plays {}.", round + 1, m.pidx(), card_name(m.card()));
+                if position == 3 {
+                    if game_info.current_pidx() != trick.winner_pidx() {
+                        return Err(HJError::InvalidDealError(format!("Trick {} is recorded with the wrong winner", round + 1)));
+                    }
+                    description += &format!(" Player {} takes the trick.", trick.winner_pidx());
+                }
+                if round == 12 && position == 3 {
+                    description += &format!(" Final scores: {:?}.", record.scores());
+                }
+                steps.push(Step { game_info: game_info.clone(), passed: direction != PassDirection::None, description });
+            }
+        }
+
+        Ok(Self { record, steps })
+    }
+
+    // Shows the game from `seat`'s side, or with every hand open when there is none, until the viewer quits.
+    pub fn run(&self, seat: Option<usize>) -> Result<(), HJError> {
+        let shared = Screen::shared()?;
+        let mut screen = shared.lock().expect("The shared screen should not be poisoned.");
+        let last = self.steps.len() - 1;
+        let mut step = 0;
+        let mut pidx = seat.unwrap_or(0);
+        let mut all_hands = seat.is_none();
+        let mut history = false;
+
+        loop {
+            let table = self.table_at(step, pidx, all_hands);
+            let mut prompt = Prompt::new(self.steps[step].description.clone(), 0, vec![]);
+            prompt.notice = Some(format!(
+                "Step {} of {} · {}",
+                step, last, if all_hands { "all hands".to_owned() } else { format!("as seen by player {}", pidx) },
+            ));
+            prompt.history = history;
+            prompt.help = Some(HELP);
+            screen.draw(|frame| { table.render(frame, &prompt); })?;
+
+            match screen.next_input()? {
+                Input::Quit => return Ok(()),
+                Input::Left => step = step.saturating_sub(1),
+                Input::Right => step = (step + 1).min(last),
+                Input::Up => step = (0..step).rev().find(|s| self.starts_trick(*s)).unwrap_or(0),
+                Input::Down => step = (step + 1..=last).find(|s| self.starts_trick(*s)).unwrap_or(last),
+                Input::Toggle => all_hands = !all_hands,
+                Input::Confirm => pidx = (pidx + 1) % 4,
+                Input::History => history = !history,
+                _ => {},
+            }
+        }
+    }
+
+    // Whether `step` is between two tricks, after the pass.
+    fn starts_trick(&self, step: usize) -> bool {
+        let Step { game_info, passed, .. } = &self.steps[step];
+        game_info.current_moves().is_empty() && (*passed || self.record.pass_direction() == PassDirection::None)
+    }
+
+    fn table_at(&self, step: usize, pidx: usize, all_hands: bool) -> Table {
+        let Step { game_info, passed, .. } = &self.steps[step];
+        let round = game_info.current_round();
+        let tricks: Vec<(Vec<Move>, usize)> = self.record.tricks()[..round].iter().map(|t| (t.moves().clone(), t.winner_pidx())).collect();
+        let hands: [Vec<Card>; 4] = std::array::from_fn(|p| game_info.hands()[p].cards().clone());
+
+        let mut table = Table::new();
+        table.pidx = pidx;
+        table.hand = hands[pidx].clone();
+        table.trick = game_info.current_moves().clone();
+        table.played = tricks.iter().flat_map(|(moves, _)| moves.iter().map(|m| m.card())).collect();
+        for (_, winner) in &tricks { table.tricks_won[*winner] += 1; }
+        table.points = game_info.score();
+        table.tricks = tricks;
+        table.direction = Some(self.record.pass_direction());
+        if *passed {
+            let shift = self.record.pass_direction().index_shift();
+            table.passed = self.record.passes()[pidx].clone();
+            table.received = self.record.passes()[(pidx + 4 - shift) % 4].clone();
+        }
+        if all_hands { table.hands = Some(hands); }
+        table
+    }
+}
@@ -0,0 +1,96 @@
+use std::io;
+use std::sync::{Arc, Mutex, Weak};
+
+use ratatui::crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::crossterm::execute;
+use ratatui::DefaultTerminal;
+
+use crate::game::errors::HJError;
+
+pub enum Input {
+    Left,
+    Right,
+    Up,
+    Down,
+    Toggle,
+    Confirm,
+    Back,
+    Hint,
+    History,
+    Click(u16, u16),
+    Quit,
+    Other,
+}
+
+// The terminal in full-screen mode, restored when dropped.
+pub struct Screen {
+    terminal: DefaultTerminal,
+    // The seat whose hand is showing, so hot-seat players hand over the keyboard before the next one looks.
+    pub holder: Option<usize>,
+}
+
+pub type SharedScreen = Arc<Mutex<Screen>>;
+
+// Human seats in one process play at the same terminal.
+static SHARED: Mutex<Weak<Mutex<Screen>>> = Mutex::new(Weak::new());
+
+fn screen_error(error: io::Error) -> HJError {
+    HJError::ActorError(format!("Cannot use the terminal: {}", error))
+}
+
+impl Screen {
+    fn open() -> Result<Self, HJError> {
+        let terminal = ratatui::try_init().map_err(screen_error)?;
+        execute!(io::stdout(), EnableMouseCapture).map_err(screen_error)?;
+        Ok(Self { terminal, holder: None })
+    }
+
+    // Opens the terminal for the first human seat, and shares it with the others until all of them let go.
+    pub fn shared() -> Result<SharedScreen, HJError> {
+        let mut shared = SHARED.lock().expect("The shared screen should not be poisoned.");
+        if let Some(screen) = shared.upgrade() { return Ok(screen); }
+
+        let screen = Arc::new(Mutex::new(Self::open()?));
+        *shared = Arc::downgrade(&screen);
+        Ok(screen)
+    }
+
+    pub fn draw<T, F: FnOnce(&mut ratatui::Frame) -> T>(&mut self, render: F) -> Result<T, HJError> {
+        let mut result = None;
+        self.terminal.draw(|frame| result = Some(render(frame))).map_err(screen_error)?;
+        Ok(result.expect("Drawing should call the render function."))
+    }
+
+    // Waits for a key press or click; resizes come back as `Other` so the caller redraws.
+    pub fn next_input(&mut self) -> Result<Input, HJError> {
+        loop {
+            return Ok(match event::read().map_err(screen_error)? {
+                Event::Key(key) if key.kind != KeyEventKind::Press => continue,
+                Event::Key(key) => match key.code {
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Input::Quit,
+                    KeyCode::Char('q') | KeyCode::Esc => Input::Quit,
+                    KeyCode::Left | KeyCode::Char('h') => Input::Left,
+                    KeyCode::Right | KeyCode::Char('l') => Input::Right,
+                    KeyCode::Up | KeyCode::Char('k') => Input::Up,
+                    KeyCode::Down | KeyCode::Char('j') => Input::Down,
+                    KeyCode::Char(' ') => Input::Toggle,
+                    KeyCode::Enter => Input::Confirm,
+                    KeyCode::Backspace => Input::Back,
+                    KeyCode::Char('?') => Input::Hint,
+                    KeyCode::Tab => Input::History,
+                    _ => Input::Other,
+                },
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => Input::Click(mouse.column, mouse.row),
+                Event::Mouse(_) => continue,
+                _ => Input::Other,
+            });
+        }
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+        ratatui::restore();
+    }
+}
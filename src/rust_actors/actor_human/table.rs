@@ -0,0 +1,321 @@
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+
+use crate::game::data::{Card, Hand, Move, PassDirection, Suit};
+use crate::game::errors::HJError;
+use crate::game::game_info::GameInfo;
+use crate::game::validator::Validator;
+use crate::internal::hj_validator::HJValidator;
+
+const CARD_WIDTH: u16 = 5;
+
+// Everything the human player has seen of the game so far.
+pub struct Table {
+    pub pidx: usize,
+    pub hand: Vec<Card>,
+    pub trick: Vec<Move>,
+    // The finished tricks with their winners; the last one is shown until the next card is played.
+    pub tricks: Vec<(Vec<Move>, usize)>,
+    pub played: Vec<Card>,
+    pub points: [isize; 4],
+    pub tricks_won: [usize; 4],
+    pub direction: Option<PassDirection>,
+    pub passed: Vec<Card>,
+    pub received: Vec<Card>,
+    // Every seat's cards, which only a replay can show.
+    pub hands: Option<[Vec<Card>; 4]>,
+}
+
+// What the player is asked to do: pick `count` of the `allowed` cards, or just read `message` when `count` is zero.
+pub struct Prompt {
+    pub message: String,
+    pub count: usize,
+    pub allowed: Vec<Card>,
+    pub cursor: usize,
+    pub chosen: Vec<Card>,
+    // Feedback on the last key, e.g. why a card cannot be played.
+    pub notice: Option<String>,
+    pub confirming: bool,
+    // What the engine would do, once asked.
+    pub hint: Vec<String>,
+    // Hides the hand and the passed cards while the keyboard changes hands.
+    pub hidden: bool,
+    // Shows the earlier tricks instead of the table.
+    pub history: bool,
+    // Replaces the key help under the message.
+    pub help: Option<&'static str>,
+}
+
+impl Prompt {
+    pub fn new(message: String, count: usize, allowed: Vec<Card>) -> Self {
+        Self { message, count, allowed, cursor: 0, chosen: vec![], notice: None, confirming: false, hint: vec![], hidden: false, history: false, help: None }
+    }
+}
+
+pub fn card_name(card: Card) -> String {
+    format!("{:?}{:?}", card.rank(), card.suit())
+}
+
+pub fn card_style(card: Card) -> Style {
+    match card.suit() {
+        Suit::Hearts | Suit::Diamonds => Style::default().fg(Color::Red),
+        Suit::Spades | Suit::Clubs => Style::default(),
+    }
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self {
+            pidx: 0,
+            hand: vec![],
+            trick: vec![],
+            tricks: vec![],
+            played: vec![],
+            points: [0; 4],
+            tricks_won: [0; 4],
+            direction: None,
+            passed: vec![],
+            received: vec![],
+            hands: None,
+        }
+    }
+
+    // The hand as it is laid out on screen: by suit, then by rank.
+    pub fn sorted_hand(&self) -> Vec<Card> {
+        let mut hand = self.hand.clone();
+        hand.sort_by_key(|c| (c.suit(), c.rank()));
+        hand
+    }
+
+    fn hearts_played(&self) -> bool {
+        self.played.iter().chain(self.trick.iter().map(|m| &m.1)).any(|c| c.suit() == Suit::Hearts)
+    }
+
+    // The game as far as the player knows it, enough to tell which of their cards may be played.
+    pub fn game_info(&self) -> GameInfo {
+        let mut hands = [Hand::new(vec![]), Hand::new(vec![]), Hand::new(vec![]), Hand::new(vec![])];
+        hands[self.pidx] = Hand::new(self.hand.clone());
+        GameInfo::reconstruct(
+            self.trick.clone(), self.pidx, self.tricks_won.iter().sum(),
+            hands, self.hearts_played(), [0; 4], [false; 4],
+        )
+    }
+
+    // Why the rules do not allow the player to play `card` now.
+    pub fn forbidding_rule(&self, card: Card) -> String {
+        match HJValidator::validate_move(&self.game_info(), &self.trick, Move(self.pidx, card)) {
+            Err(HJError::InvalidCardError(reason)) => reason,
+            // The validator leaves the opening lead to the game.
+            _ => "The first trick is opened with the two of clubs.".to_owned(),
+        }
+    }
+
+    // Who plays next, as far as the player can tell.
+    fn next_pidx(&self) -> Option<usize> {
+        match (self.trick.first(), self.tricks.last()) {
+            (Some(first), _) => Some((first.pidx() + self.trick.len()) % 4),
+            (None, Some((_, winner_pidx))) => Some(*winner_pidx),
+            (None, None) => None,
+        }
+    }
+
+    fn cards_left(&self, pidx: usize) -> usize {
+        let finished = self.tricks_won.iter().sum::<usize>();
+        13 - finished - self.trick.iter().filter(|m| m.pidx() == pidx).count()
+    }
+
+    // Seats as seen from the player, who sits at the bottom; play goes clockwise.
+    fn seat_name(&self, pidx: usize) -> String {
+        if pidx == self.pidx { format!("Player {} (you)", pidx) } else { format!("Player {}", pidx) }
+    }
+
+    // Draws the table and returns where each card of the hand ended up, for mouse selection.
+    pub fn render(&self, frame: &mut Frame, prompt: &Prompt) -> Vec<(Rect, Card)> {
+        let [title, middle, hand, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(13),
+            Constraint::Length(6),
+            Constraint::Length(3),
+        ]).areas(frame.area());
+        let [table, side] = Layout::horizontal([Constraint::Min(40), Constraint::Length(36)]).areas(middle);
+
+        frame.render_widget(Paragraph::new(self.title_line()).style(Style::default().add_modifier(Modifier::BOLD)), title);
+        if prompt.history {
+            self.render_history(frame, table, side);
+        } else {
+            self.render_table(frame, table);
+            self.render_side(frame, side, prompt);
+        }
+        let card_areas = self.render_hand(frame, hand, prompt);
+
+        let help = prompt.help.unwrap_or(match prompt.count {
+            _ if prompt.hidden => "Enter once the keyboard has changed hands · q to quit",
+            0 => "any key to continue · Tab for the history · q to quit",
+            1 => "←→↑↓ or mouse to choose · Enter or click to play · ? for a hint · Tab for the history · q to quit",
+            _ if prompt.confirming => "Enter to pass these cards · Backspace to change them · q to quit",
+            _ => "←→↑↓ to move · Space or click to pick · Enter to confirm · ? for a hint · Tab for the history · q to quit",
+        });
+        frame.render_widget(Paragraph::new(vec![
+            Line::from(prompt.message.clone()),
+            Line::from(Span::styled(prompt.notice.clone().unwrap_or_default(), Style::default().fg(Color::Yellow))),
+            Line::from(Span::styled(help, Style::default().fg(Color::DarkGray))),
+        ]), footer);
+
+        card_areas
+    }
+
+    fn title_line(&self) -> String {
+        let round = self.tricks_won.iter().sum::<usize>() + 1;
+        let direction = match self.direction {
+            Some(PassDirection::None) => "no passing".to_owned(),
+            Some(direction) => format!("passing {}", direction.name()),
+            None => "".to_owned(),
+        };
+        let hearts = if self.hearts_played() {
+            " · hearts broken"
+        } else {
+            ""
+        };
+        format!(" Hearts · trick {} of 13 · {}{}", round.min(13), direction, hearts)
+    }
+
+    fn render_table(&self, frame: &mut Frame, area: Rect) {
+        let [top, center, bottom] = Layout::vertical([Constraint::Length(5), Constraint::Min(3), Constraint::Length(5)]).areas(area);
+        let [left, center, right] = Layout::horizontal([Constraint::Length(20), Constraint::Min(20), Constraint::Length(20)]).areas(center);
+        let across = |area: Rect| Layout::horizontal([Constraint::Min(0), Constraint::Length(20), Constraint::Min(0)]).areas::<3>(area)[1];
+        let beside = |area: Rect| Layout::vertical([Constraint::Min(0), Constraint::Length(5), Constraint::Min(0)]).areas::<3>(area)[1];
+
+        self.render_seat(frame, across(bottom), self.pidx);
+        self.render_seat(frame, beside(left), (self.pidx + 1) % 4);
+        self.render_seat(frame, across(top), (self.pidx + 2) % 4);
+        self.render_seat(frame, beside(right), (self.pidx + 3) % 4);
+
+        // The current trick, or the last one with its winner until the next card is played.
+        let (moves, winner) = match (self.tricks.last(), self.trick.is_empty()) {
+            (Some((moves, winner)), true) => (moves.as_slice(), Some(*winner)),
+            _ => (self.trick.as_slice(), None),
+        };
+        let card_line = |pidx: usize| -> Line {
+            match moves.iter().find(|m| m.pidx() == pidx) {
+                Some(m) => {
+                    let mut style = card_style(m.card());
+                    if Some(pidx) == winner { style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED); }
+                    if moves.first().map(|m| m.pidx()) == Some(pidx) { style = style.add_modifier(Modifier::ITALIC); }
+                    Line::from(Span::styled(card_name(m.card()), style))
+                },
+                None => Line::from("·"),
+            }
+        };
+
+        let [_, row_top, row_middle, row_bottom, _] = Layout::vertical([
+            Constraint::Min(0), Constraint::Length(1), Constraint::Length(1), Constraint::Length(1), Constraint::Min(0),
+        ]).areas(center);
+        let [middle_left, _, middle_right] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(6), Constraint::Fill(1)]).areas(row_middle);
+        frame.render_widget(Paragraph::new(card_line((self.pidx + 2) % 4)).alignment(Alignment::Center), row_top);
+        frame.render_widget(Paragraph::new(card_line((self.pidx + 1) % 4)).alignment(Alignment::Right), middle_left);
+        frame.render_widget(Paragraph::new(card_line((self.pidx + 3) % 4)).alignment(Alignment::Left), middle_right);
+        frame.render_widget(Paragraph::new(card_line(self.pidx)).alignment(Alignment::Center), row_bottom);
+    }
+
+    fn render_seat(&self, frame: &mut Frame, area: Rect, pidx: usize) {
+        let mut block = Block::bordered().title(self.seat_name(pidx));
+        if self.next_pidx() == Some(pidx) { block = block.border_style(Style::default().fg(Color::Yellow)); }
+
+        let lines = vec![
+            Line::from(format!("Points: {}", self.points[pidx])),
+            Line::from(format!("Tricks: {}", self.tricks_won[pidx])),
+            Line::from(format!("Cards:  {}", self.cards_left(pidx))),
+        ];
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_side(&self, frame: &mut Frame, area: Rect, prompt: &Prompt) {
+        if let Some(hands) = &self.hands {
+            frame.render_widget(Paragraph::new(self.hands_lines(hands)).block(Block::bordered()), area);
+            return;
+        }
+
+        let mut lines = vec![Line::from(Span::styled("Played", Style::default().add_modifier(Modifier::BOLD)))];
+        for suit in Suit::all() {
+            let mut cards: Vec<Card> = self.played.iter().copied().filter(|c| c.suit() == suit).collect();
+            cards.sort();
+            let mut spans = vec![Span::raw(format!("{:?} ", suit))];
+            spans.extend(cards.iter().map(|c| Span::styled(format!("{:?} ", c.rank()), card_style(*c))));
+            lines.push(Line::from(spans));
+        }
+
+        if let Some(direction) = self.direction.filter(|d| *d != PassDirection::None && !prompt.hidden) {
+            let to_pidx = (self.pidx + direction.index_shift()) % 4;
+            let from_pidx = (self.pidx + 4 - direction.index_shift()) % 4;
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(format!("Passing {}", direction.name()), Style::default().add_modifier(Modifier::BOLD))));
+            lines.push(self.cards_line(&format!("To {}:   ", to_pidx), &self.passed));
+            lines.push(self.cards_line(&format!("From {}: ", from_pidx), &self.received));
+        }
+
+        if !prompt.hint.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Hint", Style::default().add_modifier(Modifier::BOLD))));
+            lines.extend(prompt.hint.iter().map(|line| Line::from(line.clone())));
+        }
+
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered()), area);
+    }
+
+    fn hands_lines(&self, hands: &[Vec<Card>; 4]) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+        for (pidx, hand) in hands.iter().enumerate() {
+            lines.push(Line::from(Span::styled(self.seat_name(pidx), Style::default().add_modifier(Modifier::BOLD))));
+            for suit in Suit::all() {
+                let mut cards: Vec<Card> = hand.iter().copied().filter(|c| c.suit() == suit).collect();
+                cards.sort_by(|a, b| b.cmp(a));
+                let mut spans = vec![Span::raw(format!("{:?} ", suit))];
+                spans.extend(cards.iter().map(|c| Span::styled(format!("{:?} ", c.rank()), card_style(*c))));
+                lines.push(Line::from(spans));
+            }
+        }
+        lines
+    }
+
+    fn cards_line(&self, label: &str, cards: &[Card]) -> Line<'static> {
+        let mut spans = vec![Span::raw(label.to_owned())];
+        spans.extend(cards.iter().map(|c| Span::styled(format!("{} ", card_name(*c)), card_style(*c))));
+        Line::from(spans)
+    }
+
+    fn render_hand(&self, frame: &mut Frame, area: Rect, prompt: &Prompt) -> Vec<(Rect, Card)> {
+        let block = Block::bordered().title("Your hand");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if prompt.hidden {
+            frame.render_widget(Paragraph::new("Hidden until the next player has the keyboard.").alignment(Alignment::Center), inner);
+            return vec![];
+        }
+
+        let hand = self.sorted_hand();
+        let mut card_areas = vec![];
+        let mut lines = vec![];
+        for (row, suit) in Suit::all().into_iter().enumerate() {
+            let mut spans = vec![Span::raw(format!("{:?}  ", suit))];
+            let mut x = inner.x + 3;
+            for (idx, card) in hand.iter().enumerate().filter(|(_, c)| c.suit() == suit) {
+                let mut style = card_style(*card);
+                if prompt.count > 0 && !prompt.allowed.contains(card) { style = style.fg(Color::DarkGray).add_modifier(Modifier::DIM); }
+                if prompt.chosen.contains(card) { style = style.bg(Color::Yellow).fg(Color::Black); }
+                if prompt.count > 0 && idx == prompt.cursor { style = style.add_modifier(Modifier::REVERSED); }
+
+                spans.push(Span::styled(format!("{:^4}", card_name(*card)), style));
+                spans.push(Span::raw(" "));
+                card_areas.push((Rect::new(x, inner.y + row as u16, CARD_WIDTH - 1, 1), *card));
+                x += CARD_WIDTH;
+            }
+            lines.push(Line::from(spans));
+        }
+        frame.render_widget(Paragraph::new(lines), inner);
+
+        card_areas
+    }
+}
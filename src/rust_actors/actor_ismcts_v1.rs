@@ -0,0 +1,142 @@
+use crate::{Card, PassDirection};
+use crate::rust_actors::actor_rule_v1::ActorRuleV1;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
+use crate::rust_actors::determinize::determinize_v1::DeterminizeV1;
+use crate::rust_actors::eval_round::eval_round_v1::EvalRoundV1;
+use crate::rust_actors::eval_state::eval_state_v1::EvalStateV1;
+use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
+use crate::game::actor::Actor;
+use crate::game::errors::HJError;
+use crate::game::data::Move;
+use crate::util::non_nan::NonNan;
+use std::marker::PhantomData;
+use crate::rust_actors::actor_mcts_v1::ActorMCTSV1;
+use crate::rust_actors::determinize::Determinize;
+use crate::rust_actors::eval_state::EvalState;
+use crate::rust_actors::player_state::{ExtendedPlayerStateInterface, MediasResActor};
+
+pub(crate) mod ismcts;
+
+pub use ismcts::Observers;
+
+// Information-set MCTS: unlike `ActorMCTSV1`, which averages separate searches of separate deals,
+// every iteration deals the unseen cards afresh and walks the same tree, so a card is judged
+// across all the deals it could face instead of on the deal it happened to be searched in.
+pub struct ActorISMCTSV1<
+    D: Determinize,
+    ES: EvalState,
+    S: MediasResActor<DefaultPlayerState>,
+    PS: ExtendedPlayerStateInterface
+> {
+    player_state: PS,
+
+    timeout: usize,
+    observers: Observers,
+
+    determinize_type: PhantomData<D>,
+    eval_state_type: PhantomData<ES>,
+    sub_actor_type: PhantomData<S>,
+}
+
+impl<
+    D: Determinize,
+    ES: EvalState,
+    S: MediasResActor<DefaultPlayerState>,
+    PS: ExtendedPlayerStateInterface
+> ActorISMCTSV1<D, ES, S, PS> {
+    pub fn customize(timeout: usize, observers: Observers) -> Self {
+        Self {
+            player_state: Default::default(),
+
+            timeout,
+            observers,
+
+            determinize_type: PhantomData,
+            eval_state_type: PhantomData,
+            sub_actor_type: PhantomData,
+        }
+    }
+}
+
+impl ActorISMCTSV1<DeterminizeV1, EvalStateV1, ActorRuleV1<EvalRoundV1, EvalStateV1, ExtendedPlayerState>, ExtendedPlayerState> {
+    #[allow(dead_code)]
+    pub fn new(timeout: usize) -> Self {
+        Self::customize(timeout, Observers::Single)
+    }
+
+    #[allow(dead_code)]
+    pub fn multi_observer(timeout: usize) -> Self {
+        Self::customize(timeout, Observers::Multi)
+    }
+}
+
+impl<
+    D: Determinize,
+    ES: EvalState,
+    S: MediasResActor<DefaultPlayerState>,
+    PS: ExtendedPlayerStateInterface
+> ActorISMCTSV1<D, ES, S, PS> {
+    pub fn set_player_state(&mut self, player_state: PS) {
+        self.player_state = player_state;
+    }
+
+    // Searches for `time` ms, returning the total value and visits per card.
+    pub fn search(&self, played_moves: &Vec<Move>, time: usize) -> Vec<(Card, f32, usize)> {
+        ismcts::ismcts::<D, S, PS>(self.player_state.pidx(), &self.player_state, played_moves, time, self.observers)
+    }
+
+    // Passing is not searched; the cards are chosen as `ActorMCTSV1` does.
+    pub fn choose_three_to_pass(&self) -> Vec<Card> {
+        let mut actor = ActorMCTSV1::<D, ES, S, PS>::customize(self.timeout, 1);
+        actor.set_player_state(self.player_state.clone());
+        actor.choose_three_to_pass()
+    }
+}
+
+impl<
+    D: Determinize,
+    ES: EvalState,
+    S: MediasResActor<DefaultPlayerState>,
+    PS: ExtendedPlayerStateInterface
+> Actor for ActorISMCTSV1<D, ES, S, PS> {
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError> {
+        // Actors play one game after another, so nothing is carried over from the last one.
+        self.player_state = Default::default();
+        self.player_state.set_pidx(pidx);
+        self.player_state.set_cards(cards.clone());
+        Ok(())
+    }
+
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
+        self.player_state.update_play_card(played_moves);
+
+        // The most visited card, as is usual for MCTS; its value is too noisy when it was tried only a few times.
+        let best_card = self.search(played_moves, self.timeout).into_iter().max_by_key(|(_, value, visits)| {
+            (*visits, NonNan::new(*value).unwrap())
+        }).unwrap().0;
+
+        self.player_state.update_did_play_card(&best_card);
+
+        Ok(best_card)
+    }
+
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
+        self.player_state.update_end_round(played_moves, winner_pidx);
+        Ok(())
+    }
+
+    fn end_game(&mut self, _score: [isize; 4]) -> Result<(), HJError> { Ok(()) }
+
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError> {
+        if direction == PassDirection::None { return Ok(vec![]); }
+
+        let passed_cards = self.choose_three_to_pass();
+        self.player_state.update_did_pass(&passed_cards, direction);
+        Ok(passed_cards)
+    }
+
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError> {
+        self.player_state.update_end_pass(passed_cards);
+        Ok(())
+    }
+}
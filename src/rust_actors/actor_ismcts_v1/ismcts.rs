@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
+
+use crate::Card;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
+use crate::game::data::Move;
+use crate::game::game_info::{GameInfo, StopCondition};
+use crate::rust_actors::determinize::Determinize;
+use crate::rust_actors::player_state::DefaultPlayerStateInterface;
+use crate::rust_actors::player_state::MediasResActor;
+use crate::rust_actors::util::get_allowed_cards;
+use crate::util::non_nan::NonNan;
+use crate::util::platform::Instant;
+use crate::util::rng;
+
+// Which information sets the search keeps trees for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Observers {
+    // One tree over the searching player's information sets, in which every seat chooses its cards.
+    Single,
+    // One tree per seat, each over that seat's information sets; a seat only chooses in its own tree.
+    Multi,
+}
+
+// A node is reached by playing a card, so its statistics belong to the seat that played it.
+struct Node {
+    visits: usize,
+    value: f32,
+    // How often the card leading here was legal when the parent was visited; determinizations differ in what can be played.
+    available: usize,
+    children: HashMap<Card, Node>,
+}
+
+impl Node {
+    pub fn new() -> Node {
+        Node {
+            visits: 0,
+            value: 0.,
+            available: 0,
+            children: HashMap::new(),
+        }
+    }
+
+    pub fn search_weight(&self) -> f32 {
+        (self.value / self.visits as f32) + (2. * (self.available as f32).ln() / self.visits as f32).sqrt()
+    }
+
+    pub fn update(&mut self, result: [isize; 4], pidx: usize) {
+        self.visits += 1;
+        self.value += (36 - result[pidx]) as f32 / 46.;
+    }
+
+    // Picks a card that was never tried if there is one, else the best one by UCB among those legal now.
+    fn choose(&mut self, possible_cards: &[Card]) -> (Card, bool) {
+        for card in possible_cards {
+            if let Some(child) = self.children.get_mut(card) { child.available += 1; }
+        }
+
+        let untried: Vec<Card> = possible_cards.iter().filter(|c| !self.children.contains_key(c)).copied().collect();
+        if let Some(card) = rng::with_rng(|rng| untried.choose(rng)) {
+            self.children.entry(*card).or_insert_with(Node::new).available += 1;
+            return (*card, true);
+        }
+
+        let card = possible_cards.iter().max_by_key(|c| NonNan::new(self.children[c].search_weight()).unwrap()).unwrap();
+        (*card, false)
+    }
+}
+
+fn play_one<S: MediasResActor<DefaultPlayerState>>(game_info: &mut GameInfo, actors: &mut [&mut S; 4], card: Card) {
+    actors[game_info.current_pidx()].add_dummy(card);
+    game_info.play_without_validator(actors, StopCondition::OneMove);
+}
+
+fn play_randomly<S: MediasResActor<DefaultPlayerState>>(game_info: &mut GameInfo, actors: &mut [&mut S; 4]) -> [isize; 4] {
+    game_info.play_without_validator(actors, StopCondition::None);
+    game_info.result().unwrap()
+}
+
+// Walks down the trees in step, one node per tree, and returns the result of the game.
+fn ismcts_rec<S: MediasResActor<DefaultPlayerState>>(nodes: &mut [&mut Node], game_info: &mut GameInfo, actors: &mut [&mut S; 4]) -> [isize; 4] {
+    let pidx = game_info.current_pidx();
+    let possible_cards = get_allowed_cards(game_info);
+    let chooser = if nodes.len() == 1 { 0 } else { pidx };
+    let (card, expanded) = nodes[chooser].choose(&possible_cards);
+
+    play_one::<S>(game_info, actors, card);
+
+    // Played cards are seen by everyone, so every tree follows the same card.
+    let mut children: Vec<&mut Node> = nodes.iter_mut().map(|node| node.children.entry(card).or_insert_with(Node::new)).collect();
+    let result = match game_info.result() {
+        Some(result) => result,
+        None if expanded => play_randomly::<S>(game_info, actors),
+        None => ismcts_rec::<S>(&mut children, game_info, actors),
+    };
+
+    for child in children { child.update(result, pidx); }
+    result
+}
+
+pub fn ismcts<
+    D: Determinize,
+    S: MediasResActor<DefaultPlayerState>,
+    PS: DefaultPlayerStateInterface
+>(pidx: usize, player_state: &PS, played_moves: &Vec<Move>, time: usize, observers: Observers) -> Vec<(Card, f32, usize)> {
+    let mut roots: Vec<Node> = match observers {
+        Observers::Single => vec![Node::new()],
+        Observers::Multi => (0..4).map(|_| Node::new()).collect(),
+    };
+
+    // At least one iteration, so there is always a card to play.
+    let start_time = Instant::now();
+    loop {
+        // Every iteration searches a fresh deal of the unseen cards.
+        let (mut game_info, player_states) = D::determinize(pidx, player_state, played_moves);
+        let mut actors = [
+            &mut S::new_from_player_state(&player_states[0]),
+            &mut S::new_from_player_state(&player_states[1]),
+            &mut S::new_from_player_state(&player_states[2]),
+            &mut S::new_from_player_state(&player_states[3]),
+        ];
+        ismcts_rec::<S>(&mut roots.iter_mut().collect::<Vec<_>>(), &mut game_info, &mut actors);
+        if start_time.elapsed().as_millis() >= time as u128 { break; }
+    }
+
+    let root = if observers == Observers::Single { &roots[0] } else { &roots[pidx] };
+    root.children.iter().map(|(c, node)| (*c, node.value, node.visits)).collect()
+}
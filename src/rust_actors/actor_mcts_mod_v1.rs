@@ -1,5 +1,12 @@
-use crate::{ActorRuleV1, Card, DefaultPlayerState, DeterminizeV1, EvalRoundV1, EvalStateV1, ExtendedPlayerState, PassDirection, Rank, Suit};
+use crate::{Card, PassDirection, Rank, Suit};
+use crate::rust_actors::actor_rule_v1::ActorRuleV1;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
+use crate::rust_actors::determinize::determinize_v1::DeterminizeV1;
+use crate::rust_actors::eval_round::eval_round_v1::EvalRoundV1;
+use crate::rust_actors::eval_state::eval_state_v1::EvalStateV1;
+use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
 use crate::game::actor::Actor;
+use crate::game::errors::HJError;
 use crate::game::data::Move;
 use crate::util::non_nan::NonNan;
 use itertools::Itertools;
@@ -10,7 +17,7 @@ use crate::rust_actors::determinize::{Determinize, determinize_v1};
 use crate::rust_actors::eval_state::EvalState;
 use crate::rust_actors::player_state::{ExtendedPlayerStateInterface, MediasResActor};
 
-mod mcts_mod;
+pub(crate) mod mcts_mod;
 
 pub struct ActorMCTSModV1<
     D: Determinize,
@@ -76,6 +83,15 @@ impl<
             .collect()
     }
 
+    pub fn set_player_state(&mut self, player_state: PS) {
+        self.player_state = player_state;
+    }
+
+    // Searches for `time` ms, returning the total value and visits per card.
+    pub fn search(&self, played_moves: &Vec<Move>, time: usize) -> Vec<(Card, f32, usize)> {
+        mcts_mod::mcts_mod::<D, S, PS>(self.player_state.pidx(), &self.player_state, played_moves, time)
+    }
+
     pub fn choose_three_to_pass(&self) -> Vec<Card> {
         let mut by_suit = [vec![], vec![], vec![], vec![]];
         let mut by_suit_counts = [0, 0, 0, 0];
@@ -132,38 +148,43 @@ impl<
     S: MediasResActor<DefaultPlayerState>,
     PS: ExtendedPlayerStateInterface
 > Actor for ActorMCTSModV1<D, ES, S, PS> {
-    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) {
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError> {
+        // Actors play one game after another, so nothing is carried over from the last one.
+        self.player_state = Default::default();
         self.player_state.set_pidx(pidx);
         self.player_state.set_cards(cards.clone());
+        Ok(())
     }
 
-    fn play_card(&mut self, played_moves: &Vec<Move>) -> Card {
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
         self.player_state.update_play_card(played_moves);
 
-        let best_card = mcts_mod::mcts_mod::<D, S, PS>(self.player_state.pidx(), &mut self.player_state, played_moves, self.timeout).into_iter().max_by_key(|(_, value, visits)| {
+        let best_card = self.search(played_moves, self.timeout).into_iter().max_by_key(|(_, value, visits)| {
             NonNan::new(if *visits == 0 { 0. } else { value / *visits as f32 }).unwrap()
         }).unwrap().0;
 
         self.player_state.update_did_play_card(&best_card);
 
-        best_card
+        Ok(best_card)
     }
 
-    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) {
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
         self.player_state.update_end_round(played_moves, winner_pidx);
+        Ok(())
     }
 
-    fn end_game(&mut self, _score: [isize; 4]) {}
+    fn end_game(&mut self, _score: [isize; 4]) -> Result<(), HJError> { Ok(()) }
 
-    fn get_pass(&mut self, direction: PassDirection) -> Vec<Card> {
-        if direction == PassDirection::None { return vec![]; }
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError> {
+        if direction == PassDirection::None { return Ok(vec![]); }
 
         let passed_cards = self.choose_three_to_pass();
-        for passed_card in &passed_cards { self.player_state.update_did_play_card(passed_card); }
-        passed_cards
+        self.player_state.update_did_pass(&passed_cards, direction);
+        Ok(passed_cards)
     }
 
-    fn end_pass(&mut self, passed_cards: &Vec<Card>) {
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError> {
         self.player_state.update_end_pass(passed_cards);
+        Ok(())
     }
 }
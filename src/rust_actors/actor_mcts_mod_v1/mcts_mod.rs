@@ -2,10 +2,11 @@ use crate::game::data::Move;
 use crate::game::actor::Actor;
 use crate::util::deck::find_winner_pidx;
 
-use std::time::SystemTime;
+use crate::util::platform::Instant;
 use std::collections::HashMap;
 
-use crate::{Card, Rank, ActorRuleV1, Suit, ExtendedPlayerState, DefaultPlayerState};
+use crate::Card;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
 use crate::game::game_info::{GameInfo, StopCondition};
 use crate::rust_actors::actor_dummy::ActorDummy;
 use crate::rust_actors::determinize::Determinize;
@@ -132,8 +133,8 @@ pub fn mcts_mod<
         player_state.cards().clone(),
     );
 
-    let start_time = SystemTime::now();
-    while SystemTime::now().duration_since(start_time).unwrap().as_millis() < time as u128 {
+    let start_time = Instant::now();
+    while start_time.elapsed().as_millis() < time as u128 {
         let (mut game_info, mut player_states) = D::determinize(pidx, player_state, played_moves);
         let mut actors = [
             &mut S::new_from_player_state(&player_states[0]),
@@ -2,8 +2,9 @@ use crate::game::data::Move;
 use crate::game::actor::Actor;
 use crate::util::non_nan::NonNan;
 
-use std::time::SystemTime;
-use crate::{Card, Rank, ActorRuleV1, Suit, ExtendedPlayerState, DefaultPlayerState, EvalStateV1, EvalRoundV1};
+use crate::util::platform::Instant;
+use crate::Card;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
 use crate::game::game_info::{GameInfo, StopCondition};
 use crate::rust_actors::actor_dummy::ActorDummy;
 use crate::rust_actors::player_state::DefaultPlayerStateInterface;
@@ -45,6 +46,7 @@ impl Node {
         &mut self.children
     }
 
+    #[allow(dead_code)]
     pub fn game_info_mut(&mut self) -> &mut GameInfo {
         &mut self.game_info
     }
@@ -166,8 +168,8 @@ pub fn mcts<S: MediasResActor<DefaultPlayerState>>(
         None,
     );
 
-    let start_time = SystemTime::now();
-    while SystemTime::now().duration_since(start_time).unwrap().as_millis() < time as u128 {
+    let start_time = Instant::now();
+    while start_time.elapsed().as_millis() < time as u128 {
         let result = mcts_rec::<S>(&mut root);
         root.update(result);
     }
@@ -1,11 +1,13 @@
 use core::clone::Clone;
 use core::iter::IntoIterator;
 use crate::game::actor::Actor;
+use crate::game::errors::HJError;
 use crate::game::data::{Card, Move, Rank, Suit};
 
 use rand::seq::SliceRandom;
 use rand::thread_rng;
-use crate::{BasicPlayerState, PassDirection};
+use crate::PassDirection;
+use crate::rust_actors::player_state::basic_player_state::BasicPlayerState;
 use crate::rust_actors::player_state::{BasicPlayerStateInterface, MediasResActor};
 
 pub struct ActorRandom<PlayerState: BasicPlayerStateInterface> {
@@ -24,12 +26,15 @@ impl ActorRandom<BasicPlayerState> {
 }
 
 impl<PlayerState: BasicPlayerStateInterface> Actor for ActorRandom<PlayerState> {
-    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) {
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError> {
+        // Actors play one game after another, so nothing is carried over from the last one.
+        self.player_state = Default::default();
         self.player_state.set_pidx(pidx);
         self.player_state.set_cards(cards.clone());
+        Ok(())
     }
 
-    fn play_card(&mut self, played_moves: &Vec<Move>) -> Card {
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
         self.player_state.update_play_card(played_moves);
         self.player_state.cards_mut().shuffle(&mut thread_rng());
 
@@ -54,19 +59,20 @@ impl<PlayerState: BasicPlayerStateInterface> Actor for ActorRandom<PlayerState>
         };
 
         self.player_state.update_did_play_card(&new_card);
-        new_card
+        Ok(new_card)
     }
 
-    fn end_round(&mut self, _winner_pidx: usize, played_moves: &Vec<Move>) {
+    fn end_round(&mut self, _winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
         self.player_state.set_first_round(false);
         if played_moves.into_iter().any(|m| m.card().suit() == Suit::Hearts) {
             self.player_state.set_hearts_played(true);
         }
+        Ok(())
     }
 
-    fn end_game(&mut self, _score: [isize; 4]) {}
+    fn end_game(&mut self, _score: [isize; 4]) -> Result<(), HJError> { Ok(()) }
 
-    fn get_pass(&mut self, direction: PassDirection) -> Vec<Card> {
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError> {
         let passed_cards = match direction {
             PassDirection::None => vec![],
             _ => {
@@ -74,12 +80,13 @@ impl<PlayerState: BasicPlayerStateInterface> Actor for ActorRandom<PlayerState>
                 self.player_state.cards().iter().take(3).map(|c| *c).collect()
             }
         };
-        for passed_card in &passed_cards { self.player_state.update_did_play_card(passed_card); }
-        passed_cards
+        self.player_state.update_did_pass(&passed_cards, direction);
+        Ok(passed_cards)
     }
 
-    fn end_pass(&mut self, passed_cards: &Vec<Card>) {
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError> {
         self.player_state.update_end_pass(passed_cards);
+        Ok(())
     }
 }
 
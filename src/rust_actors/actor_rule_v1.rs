@@ -1,6 +1,10 @@
 use iter_fixed::IntoIteratorFixed;
-use crate::{EvalRoundV1, EvalStateV1, ExtendedPlayerState, PassDirection};
+use crate::PassDirection;
+use crate::rust_actors::eval_round::eval_round_v1::EvalRoundV1;
+use crate::rust_actors::eval_state::eval_state_v1::EvalStateV1;
+use crate::rust_actors::player_state::extended_player_state::ExtendedPlayerState;
 use crate::game::actor::Actor;
+use crate::game::errors::HJError;
 use crate::game::data::{Card, Move, Rank, Suit};
 use crate::util::non_nan::NonNan;
 use std::marker::PhantomData;
@@ -115,6 +119,41 @@ impl<
         result.retain(|c| !best_option.contains(&c));
         result
     }
+
+    // What the round and the cards left in hand would cost after each playable card; lower is better.
+    // The player state should already have seen `played_moves`.
+    pub fn card_costs(&self, played_moves: &Vec<Move>) -> Vec<(Card, NonNan)> {
+        let possible_cards = if let Some(first_move) = played_moves.first() {
+            let same_suit_cards: Vec<Card> = self.player_state.cards().iter().filter(|c| c.suit() == first_move.card().suit()).map(|c| *c).collect();
+            if same_suit_cards.len() > 0 {
+                same_suit_cards
+            } else {
+                let non_scoring_cards: Vec<Card> = self.player_state.cards().iter().filter(|c| c.score() == 0).map(|c| *c).collect();
+                if self.player_state.first_round() && non_scoring_cards.len() > 0 {
+                    non_scoring_cards
+                } else {
+                    self.player_state.cards().clone()
+                }
+            }
+        } else {
+            if self.player_state.first_round() {
+                vec![Card(Rank::Two, Suit::Clubs)]
+            } else {
+                let non_heart_cards: Vec<Card> = self.player_state.cards().iter().filter(|c| c.suit() != Suit::Hearts).map(|c| *c).collect();
+                if !self.player_state.hearts_played() && non_heart_cards.len() > 0 {
+                    non_heart_cards
+                } else {
+                    self.player_state.cards().clone()
+                }
+            }
+        };
+
+        possible_cards.into_iter().map(|c| {
+            let mut by_suit = [vec![], vec![], vec![], vec![]];
+            for card in self.player_state.cards() { if *card != c { by_suit[card.suit() as usize - 1].push(*card); } }
+            (c, ER::evaluate_round(&self.player_state, played_moves, c).try_add(ES::evaluate_state(&self.player_state, &by_suit)).unwrap())
+        }).collect()
+    }
 }
 
 impl<
@@ -122,71 +161,47 @@ impl<
     ES: EvalState,
     PS: ExtendedPlayerStateInterface
 > Actor for ActorRuleV1<ER, ES, PS> {
-    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) {
+    fn initialize(&mut self, pidx: usize, cards: &Vec<Card>) -> Result<(), HJError> {
+        // Actors play one game after another, so nothing is carried over from the last one.
+        self.player_state = Default::default();
         self.player_state.set_pidx(pidx);
         self.player_state.set_cards(cards.clone());
+        Ok(())
     }
 
-    fn play_card(&mut self, played_moves: &Vec<Move>) -> Card {
+    fn play_card(&mut self, played_moves: &Vec<Move>) -> Result<Card, HJError> {
         self.player_state.update_play_card(played_moves);
 
         let best_card = if let Some(card) = self.dummy {
             self.dummy = None;
             card
         } else {
-            let possible_cards = if let Some(first_move) = played_moves.first() {
-                let same_suit_cards: Vec<Card> = self.player_state.cards().iter().filter(|c| c.suit() == first_move.card().suit()).map(|c| *c).collect();
-                if same_suit_cards.len() > 0 {
-                    same_suit_cards
-                } else {
-                    let non_scoring_cards: Vec<Card> = self.player_state.cards().iter().filter(|c| c.score() == 0).map(|c| *c).collect();
-                    if self.player_state.first_round() && non_scoring_cards.len() > 0 {
-                        non_scoring_cards
-                    } else {
-                        self.player_state.cards().clone()
-                    }
-                }
-            } else {
-                if self.player_state.first_round() {
-                    vec![Card(Rank::Two, Suit::Clubs)]
-                } else {
-                    let non_heart_cards: Vec<Card> = self.player_state.cards().iter().filter(|c| c.suit() != Suit::Hearts).map(|c| *c).collect();
-                    if !self.player_state.hearts_played() && non_heart_cards.len() > 0 {
-                        non_heart_cards
-                    } else {
-                        self.player_state.cards().clone()
-                    }
-                }
-            };
-
-            *possible_cards.iter().min_by_key(|c| {
-                let mut by_suit = [vec![], vec![], vec![], vec![]];
-                for card in self.player_state.cards() { if card != *c { by_suit[card.suit() as usize - 1].push(*card); } }
-                ER::evaluate_round(&self.player_state, played_moves, **c).try_add(ES::evaluate_state(&self.player_state, &by_suit)).unwrap()
-            }).expect("There should always be valid playing options")
+            self.card_costs(played_moves).into_iter().min_by_key(|(_, cost)| *cost).expect("There should always be valid playing options").0
         };
 
         self.player_state.update_did_play_card(&best_card);
 
-        best_card
+        Ok(best_card)
     }
 
-    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) {
+    fn end_round(&mut self, winner_pidx: usize, played_moves: &Vec<Move>) -> Result<(), HJError> {
         self.player_state.update_end_round(played_moves, winner_pidx);
+        Ok(())
     }
 
-    fn end_game(&mut self, _score: [isize; 4]) {}
+    fn end_game(&mut self, _score: [isize; 4]) -> Result<(), HJError> { Ok(()) }
 
-    fn get_pass(&mut self, direction: PassDirection) -> Vec<Card> {
-        if direction == PassDirection::None { return vec![]; }
+    fn get_pass(&mut self, direction: PassDirection) -> Result<Vec<Card>, HJError> {
+        if direction == PassDirection::None { return Ok(vec![]); }
 
         let passed_cards = self.choose_three_to_pass();
-        for passed_card in &passed_cards { self.player_state.update_did_play_card(passed_card); }
-        passed_cards
+        self.player_state.update_did_pass(&passed_cards, direction);
+        Ok(passed_cards)
     }
 
-    fn end_pass(&mut self, passed_cards: &Vec<Card>) {
+    fn end_pass(&mut self, passed_cards: &Vec<Card>) -> Result<(), HJError> {
         self.player_state.update_end_pass(passed_cards);
+        Ok(())
     }
 }
 
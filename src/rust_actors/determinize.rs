@@ -1,9 +1,10 @@
-use crate::DefaultPlayerState;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
 use crate::game::data::Move;
 use crate::game::game_info::GameInfo;
 use crate::rust_actors::player_state::DefaultPlayerStateInterface;
 
 pub mod determinize_v1;
+pub mod determinize_v2;
 
 pub trait Determinize {
     fn determinize<
@@ -1,19 +1,18 @@
-use iter_fixed::IntoIteratorFixed;
-use crate::{Card, DefaultPlayerState};
+use crate::Card;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
 
 use crate::game::data::{Hand, Move};
 
 use rand::seq::SliceRandom;
-use rand::thread_rng;
-use tap::Tap;
 use crate::game::game_info::GameInfo;
 use crate::rust_actors::determinize::Determinize;
 use crate::rust_actors::player_state::DefaultPlayerStateInterface;
 use crate::rust_actors::player_state::BasicPlayerStateInterface;
+use crate::util::rng;
 
 pub struct DeterminizeV1;
 impl DeterminizeV1 {
-    fn new_player_state<PS: DefaultPlayerStateInterface>(cards: Vec<Card>, other_state: &PS) -> DefaultPlayerState {
+    pub(crate) fn new_player_state<PS: DefaultPlayerStateInterface>(cards: Vec<Card>, other_state: &PS) -> DefaultPlayerState {
         DefaultPlayerState::new(
             cards,
             other_state.first_round(),
@@ -39,32 +38,26 @@ impl Determinize for DeterminizeV1 {
             .filter(|c| opponent_cards_in_game[c.suit().to_index()][c.rank().to_index()])
             .map(|c| *c)
             .collect::<Vec<Card>>();
-        opponent_cards_left.shuffle(&mut thread_rng());
 
         let num_if_played = opponent_cards_left.len() / 3;
         let num_if_not_played = num_if_played + if opponent_cards_left.len() % 3 == 0 { 0 } else { 1 };
 
-        let ps0 = if pidx == 0 { Self::new_player_state(player_state.cards().clone(), player_state) } else {
-            opponent_cards_left.sort_by_key(|c| (!player_state.still_has()[c.suit().to_index()][0]) as usize);
-            Self::new_player_state(
-                opponent_cards_left.drain(
-                    if has_played[0] { 0..num_if_played } else { 0..num_if_not_played }
-                ).collect(),
-                player_state,
-            ).tap(|_| opponent_cards_left.shuffle(&mut thread_rng()))
-        };
-        let ps1 = if pidx == 1 { Self::new_player_state(player_state.cards().clone(), player_state) } else {
-            opponent_cards_left.sort_by_key(|c| (!player_state.still_has()[c.suit().to_index()][1]) as usize);
-            Self::new_player_state(opponent_cards_left.drain(if has_played[1] { 0..num_if_played } else { 0..num_if_not_played }).collect(), player_state)
-                .tap(|_| opponent_cards_left.shuffle(&mut thread_rng()))
-        };
-        let ps2 = if pidx == 2 { Self::new_player_state(player_state.cards().clone(), player_state) } else {        opponent_cards_left.sort_by_key(|c| (!player_state.still_has()[c.suit().to_index()][2]) as usize);
-            Self::new_player_state(opponent_cards_left.drain(if has_played[2] { 0..num_if_played } else { 0..num_if_not_played }).collect(), player_state).tap(|_| opponent_cards_left.shuffle(&mut thread_rng())) };
-        let ps3 = if pidx == 3 { Self::new_player_state(player_state.cards().clone(), player_state) } else {         opponent_cards_left.sort_by_key(|c| (!player_state.still_has()[c.suit().to_index()][3]) as usize);
-            Self::new_player_state(opponent_cards_left.drain(if has_played[3] { 0..num_if_played } else { 0..num_if_not_played }).collect(), player_state) };
+        // The cards we passed are still with the seat we passed them to.
+        opponent_cards_left.retain(|c| !player_state.passed_cards().contains(c));
+        rng::with_rng(|rng| opponent_cards_left.shuffle(rng));
 
-        let hands = [Hand::new(ps0.cards().clone()), Hand::new(ps1.cards().clone()), Hand::new(ps2.cards().clone()), Hand::new(ps3.cards().clone())];
-        let player_states = [ps0, ps1, ps2, ps3];
+        let player_states = [0, 1, 2, 3].map(|opponent| {
+            if opponent == pidx { return Self::new_player_state(player_state.cards().clone(), player_state); }
+
+            let mut cards = if opponent == player_state.passed_pidx() { player_state.passed_cards().clone() } else { vec![] };
+            let num = if has_played[opponent] { num_if_played } else { num_if_not_played };
+            opponent_cards_left.sort_by_key(|c| (!player_state.still_has()[c.suit().to_index()][opponent]) as usize);
+            cards.extend(opponent_cards_left.drain(0..num.saturating_sub(cards.len())));
+            rng::with_rng(|rng| opponent_cards_left.shuffle(rng));
+            Self::new_player_state(cards, player_state)
+        });
+
+        let hands = player_states.clone().map(|ps| Hand::new(ps.cards().clone()));
         (
             GameInfo::reconstruct(played_moves.clone(), pidx, 13 - player_state.cards().len(), hands, player_state.hearts_played(), player_state.scores().clone(), player_state.scored().clone()),
             player_states
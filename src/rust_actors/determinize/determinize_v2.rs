@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+
+use crate::Card;
+use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
+use crate::game::data::{Hand, Move};
+use crate::game::game_info::GameInfo;
+use crate::rust_actors::determinize::Determinize;
+use crate::rust_actors::determinize::determinize_v1::DeterminizeV1;
+use crate::rust_actors::player_state::DefaultPlayerStateInterface;
+use crate::util::rng;
+
+// With this few unseen cards, every consistent deal is listed and one of them picked.
+const EXACT_LIMIT: usize = 6;
+// Plain shuffles tried before counting deals, which is slower but does not depend on luck.
+const SHUFFLE_TRIES: usize = 20;
+const FACTORIALS: [f64; 14] = [
+    1., 1., 2., 6., 24., 120., 720., 5040., 40320., 362880., 3628800., 39916800., 479001600., 6227020800.,
+];
+
+// What a player knows about where the unseen cards are: the cards whose holder is known, the
+// suits each seat has shown void in, and how many cards each seat holds.
+#[derive(Clone, Debug)]
+pub struct UnseenCards {
+    // Cards whose holder is not known, by suit.
+    unknown: [Vec<Card>; 4],
+    known: [Vec<Card>; 4],
+    // How many of the unknown cards each seat holds.
+    needs: [usize; 4],
+    // Whether a seat may still hold cards of a suit, by suit and seat.
+    may_hold: [[bool; 4]; 4],
+}
+
+impl UnseenCards {
+    // None when the hand sizes do not add up to the cards there are.
+    pub fn new(unknown: Vec<Card>, known: [Vec<Card>; 4], sizes: [usize; 4], may_hold: [[bool; 4]; 4]) -> Option<Self> {
+        let mut needs = [0; 4];
+        for pidx in 0..4 { needs[pidx] = sizes[pidx].checked_sub(known[pidx].len())?; }
+        if needs.iter().sum::<usize>() != unknown.len() { return None; }
+
+        let mut by_suit: [Vec<Card>; 4] = [vec![], vec![], vec![], vec![]];
+        for card in unknown { by_suit[card.suit().to_index()].push(card); }
+        Some(Self { unknown: by_suit, known, needs, may_hold })
+    }
+
+    // Everything `pidx` knows when it is to play after `played_moves`: its own cards, the cards it passed, and the voids shown so far.
+    pub fn from_player_state<PS: DefaultPlayerStateInterface>(pidx: usize, player_state: &PS, played_moves: &[Move]) -> Option<Self> {
+        let mut known: [Vec<Card>; 4] = [vec![], vec![], vec![], vec![]];
+        known[pidx] = player_state.cards().clone();
+        if player_state.passed_pidx() != pidx { known[player_state.passed_pidx()].extend(player_state.passed_cards()); }
+
+        let unknown: Vec<Card> = Card::all().into_iter()
+            .filter(|c| player_state.cards_in_game()[c.suit().to_index()][c.rank().to_index()])
+            .filter(|c| !known.iter().any(|cards| cards.contains(c)))
+            .collect();
+
+        // Everyone holds as many cards as we do, less the one they already put on this trick.
+        let sizes: [usize; 4] = std::array::from_fn(|p| {
+            player_state.cards().len() - played_moves.iter().filter(|m| m.pidx() == p).count()
+        });
+
+        Self::new(unknown, known, sizes, *player_state.still_has())
+    }
+
+    pub fn unknown_count(&self) -> usize {
+        self.unknown.iter().map(|cards| cards.len()).sum()
+    }
+
+    // Whether `hands` could be the actual deal.
+    #[allow(dead_code)]
+    pub fn is_consistent(&self, hands: &[Vec<Card>; 4]) -> bool {
+        let mut seen: Vec<Card> = hands.iter().flatten().copied().collect();
+        seen.sort();
+        seen.dedup();
+        let mut expected: Vec<Card> = self.unknown.iter().chain(self.known.iter()).flatten().copied().collect();
+        expected.sort();
+
+        seen.len() == hands.iter().map(|h| h.len()).sum::<usize>()
+            && seen == expected
+            && (0..4).all(|pidx| {
+                hands[pidx].len() == self.known[pidx].len() + self.needs[pidx]
+                    && self.known[pidx].iter().all(|c| hands[pidx].contains(c))
+                    && hands[pidx].iter().filter(|c| !self.known[pidx].contains(c)).all(|c| self.may_hold[c.suit().to_index()][pidx])
+            })
+    }
+
+    // The number of consistent deals.
+    #[allow(dead_code)]
+    pub fn count(&self) -> f64 {
+        Counter::new(self).ways(0, self.needs)
+    }
+
+    // Draws one of the consistent deals, each equally likely, or None when there are none.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<[Vec<Card>; 4]> {
+        (0..SHUFFLE_TRIES).find_map(|_| self.sample_by_shuffling(rng)).or_else(|| self.sample_by_counting(rng))
+    }
+
+    // Deals a shuffled deck by hand sizes alone, and gives up if that breaks a void.
+    // Rejecting the broken deals keeps the rest uniform.
+    pub fn sample_by_shuffling<R: Rng>(&self, rng: &mut R) -> Option<[Vec<Card>; 4]> {
+        let mut cards: Vec<Card> = self.unknown.iter().flatten().copied().collect();
+        cards.shuffle(rng);
+
+        let mut hands = self.known.clone();
+        for (pidx, hand) in hands.iter_mut().enumerate() {
+            let dealt = cards.drain(0..self.needs[pidx]);
+            if dealt.as_slice().iter().any(|c| !self.may_hold[c.suit().to_index()][pidx]) { return None; }
+            hand.extend(dealt);
+        }
+        Some(hands)
+    }
+
+    // Cards of a suit are interchangeable as far as the voids go, so this first picks how many
+    // cards of each suit every seat gets, weighted by the number of deals with those counts.
+    pub fn sample_by_counting<R: Rng>(&self, rng: &mut R) -> Option<[Vec<Card>; 4]> {
+        let mut counter = Counter::new(self);
+        let mut needs = self.needs;
+        let mut hands = self.known.clone();
+
+        for suit in 0..4 {
+            let splits = self.splits(suit, needs);
+            let weights: Vec<f64> = splits.iter()
+                .map(|split| multinomial(split) * counter.ways(suit + 1, subtract(needs, split)))
+                .collect();
+            let split = splits[WeightedIndex::new(&weights).ok()?.sample(rng)];
+
+            let mut cards = self.unknown[suit].clone();
+            cards.shuffle(rng);
+            for pidx in 0..4 { hands[pidx].extend(cards.drain(0..split[pidx])); }
+            needs = subtract(needs, &split);
+        }
+
+        Some(hands)
+    }
+
+    // Every consistent deal; only sensible for a handful of unknown cards.
+    pub fn enumerate(&self) -> Vec<[Vec<Card>; 4]> {
+        let unknown: Vec<Card> = self.unknown.iter().flatten().copied().collect();
+        let mut deals = vec![];
+        self.enumerate_rec(&unknown, &mut self.known.clone(), &mut self.needs.clone(), &mut deals);
+        deals
+    }
+
+    fn enumerate_rec(&self, unknown: &[Card], hands: &mut [Vec<Card>; 4], needs: &mut [usize; 4], deals: &mut Vec<[Vec<Card>; 4]>) {
+        let Some((card, rest)) = unknown.split_first() else {
+            deals.push(hands.clone());
+            return;
+        };
+
+        for pidx in 0..4 {
+            if needs[pidx] == 0 || !self.may_hold[card.suit().to_index()][pidx] { continue; }
+            hands[pidx].push(*card);
+            needs[pidx] -= 1;
+            self.enumerate_rec(rest, hands, needs, deals);
+            needs[pidx] += 1;
+            hands[pidx].pop();
+        }
+    }
+
+    // The ways to share out the unknown cards of `suit` among the seats that may hold them.
+    fn splits(&self, suit: usize, needs: [usize; 4]) -> Vec<[usize; 4]> {
+        let mut splits = vec![];
+        let mut split = [0; 4];
+        self.splits_rec(suit, 0, self.unknown[suit].len(), needs, &mut split, &mut splits);
+        splits
+    }
+
+    fn splits_rec(&self, suit: usize, pidx: usize, left: usize, needs: [usize; 4], split: &mut [usize; 4], splits: &mut Vec<[usize; 4]>) {
+        let room = |p: usize| if self.may_hold[suit][p] { needs[p] } else { 0 };
+        if pidx == 3 {
+            if left <= room(3) {
+                split[3] = left;
+                splits.push(*split);
+                split[3] = 0;
+            }
+            return;
+        }
+
+        // This seat takes at least what the later seats have no room for.
+        let later: usize = (pidx + 1..4).map(room).sum();
+        for count in left.saturating_sub(later)..=room(pidx).min(left) {
+            split[pidx] = count;
+            self.splits_rec(suit, pidx + 1, left - count, needs, split, splits);
+        }
+        split[pidx] = 0;
+    }
+}
+
+// Counts the deals of the suits from some suit onwards that fill the seats' remaining needs exactly.
+struct Counter<'a> {
+    unseen: &'a UnseenCards,
+    memo: HashMap<(usize, [usize; 4]), f64>,
+}
+
+impl<'a> Counter<'a> {
+    fn new(unseen: &'a UnseenCards) -> Self {
+        Self { unseen, memo: HashMap::new() }
+    }
+
+    fn ways(&mut self, suit: usize, needs: [usize; 4]) -> f64 {
+        if suit == 4 { return if needs == [0; 4] { 1. } else { 0. }; }
+        if let Some(ways) = self.memo.get(&(suit, needs)) { return *ways; }
+
+        let ways = self.unseen.splits(suit, needs).iter()
+            .map(|split| multinomial(split) * self.ways(suit + 1, subtract(needs, split)))
+            .sum();
+        self.memo.insert((suit, needs), ways);
+        ways
+    }
+}
+
+fn subtract(needs: [usize; 4], split: &[usize; 4]) -> [usize; 4] {
+    std::array::from_fn(|pidx| needs[pidx] - split[pidx])
+}
+
+// The ways to deal `split.iter().sum()` distinct cards into groups of the given sizes.
+fn multinomial(split: &[usize; 4]) -> f64 {
+    FACTORIALS[split.iter().sum::<usize>()] / split.iter().map(|n| FACTORIALS[*n]).product::<f64>()
+}
+
+// Deals the unseen cards uniformly among the deals that fit everything the player knows.
+pub struct DeterminizeV2;
+
+impl Determinize for DeterminizeV2 {
+    fn determinize<PS: DefaultPlayerStateInterface>(pidx: usize, player_state: &PS, played_moves: &Vec<Move>) -> (GameInfo, [DefaultPlayerState; 4]) {
+        let hands = UnseenCards::from_player_state(pidx, player_state, played_moves).and_then(|unseen| {
+            if unseen.unknown_count() <= EXACT_LIMIT {
+                rng::with_rng(|rng| unseen.enumerate().choose(rng).cloned())
+            } else {
+                rng::with_rng(|rng| unseen.sample(rng))
+            }
+        });
+        // Only an inconsistent player state leaves no deal; the greedy deal still gives the search something to play.
+        let Some(hands) = hands else { return DeterminizeV1::determinize(pidx, player_state, played_moves) };
+
+        let player_states = hands.clone().map(|cards| DeterminizeV1::new_player_state(cards, player_state));
+        (
+            GameInfo::reconstruct(played_moves.clone(), pidx, 13 - player_state.cards().len(), hands.map(Hand::new), player_state.hearts_played(), *player_state.scores(), *player_state.scored()),
+            player_states
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::game::data::{Rank, Suit};
+
+    fn cards(suit: Suit, ranks: &[usize]) -> Vec<Card> {
+        ranks.iter().map(|r| Card::new(Rank::from_index(*r as u8), suit)).collect()
+    }
+
+    // The unseen cards are spades, clubs and hearts; seat 0 knows its own spade and what it passed to seat 1.
+    fn unseen(may_hold: [[bool; 4]; 4]) -> Option<UnseenCards> {
+        let unknown = [cards(Suit::Spades, &[0, 1, 2]), cards(Suit::Clubs, &[3, 4]), cards(Suit::Hearts, &[5, 6, 7])].concat();
+        let known = [cards(Suit::Spades, &[12]), cards(Suit::Diamonds, &[0]), vec![], vec![]];
+        UnseenCards::new(unknown, known, [1, 3, 3, 3], may_hold)
+    }
+
+    fn cases() -> Vec<UnseenCards> {
+        let all = [true; 4];
+        [
+            [all, all, all, all],
+            // Seat 1 is void in spades and seat 3 in hearts.
+            [[true, false, true, true], all, all, [true, true, true, false]],
+            // Only seat 2 may hold clubs, and seat 3 has nothing but spades left.
+            [[true, false, true, true], [false, false, true, false], all, [true, true, true, false]],
+            // Everyone has shown void in hearts, so no deal is left.
+            [all, all, all, [false; 4]],
+        ].into_iter().map(|may_hold| unseen(may_hold).expect("The hand sizes should add up.")).collect()
+    }
+
+    fn sorted(mut hands: [Vec<Card>; 4]) -> [Vec<Card>; 4] {
+        for hand in hands.iter_mut() { hand.sort(); }
+        hands
+    }
+
+    #[test]
+    fn hand_sizes_must_add_up() {
+        let unknown = cards(Suit::Spades, &[0, 1, 2]);
+        assert!(UnseenCards::new(unknown.clone(), Default::default(), [1, 1, 1, 1], [[true; 4]; 4]).is_none());
+        assert!(UnseenCards::new(unknown, Default::default(), [0, 1, 1, 1], [[true; 4]; 4]).is_some());
+    }
+
+    #[test]
+    fn enumerated_deals_are_consistent() {
+        for unseen in cases() {
+            for hands in unseen.enumerate() { assert!(unseen.is_consistent(&hands), "{:?}", hands); }
+        }
+    }
+
+    #[test]
+    fn count_matches_enumeration() {
+        let counts: Vec<f64> = cases().iter().map(|unseen| unseen.count()).collect();
+        for (unseen, count) in cases().iter().zip(&counts) {
+            assert_eq!(*count, unseen.enumerate().len() as f64);
+        }
+        assert_eq!(counts[0], 560.);
+        assert_eq!(counts[3], 0.);
+    }
+
+    #[test]
+    fn samples_respect_voids() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for unseen in cases() {
+            for _ in 0..500 {
+                match unseen.sample(&mut rng) {
+                    Some(hands) => {
+                        assert!(unseen.is_consistent(&hands), "{:?}", hands);
+                        for (pidx, hand) in hands.iter().enumerate() {
+                            assert!(hand.iter().all(|c| unseen.known[pidx].contains(c) || unseen.may_hold[c.suit().to_index()][pidx]));
+                        }
+                    },
+                    None => assert_eq!(unseen.count(), 0.),
+                }
+            }
+        }
+    }
+
+    // A chi-square test over every deal of a case small enough to list; with the seed fixed it either
+    // always passes or always fails, and a biased sampler lands far above the bound.
+    #[test]
+    fn counting_samples_uniformly() {
+        let unseen = &cases()[1];
+        let deals = unseen.enumerate();
+        let draws_per_deal = 100;
+        let mut seen: BTreeMap<[Vec<Card>; 4], usize> = deals.into_iter().map(|hands| (sorted(hands), 0)).collect();
+
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..seen.len() * draws_per_deal {
+            let hands = unseen.sample_by_counting(&mut rng).expect("The case should have deals.");
+            *seen.get_mut(&sorted(hands)).expect("Samples should be among the enumerated deals.") += 1;
+        }
+
+        let expected = draws_per_deal as f64;
+        let chi_square: f64 = seen.values().map(|n| (*n as f64 - expected).powi(2) / expected).sum();
+        let freedom = (seen.len() - 1) as f64;
+        // About five standard deviations above the mean of the distribution.
+        assert!(chi_square < freedom + 5. * (2. * freedom).sqrt(), "chi-square {} with {} degrees of freedom", chi_square, freedom);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{Card, Rank, ActorRuleV1, Suit};
+use crate::{Card, Rank, Suit};
 use crate::util::non_nan::NonNan;
+use crate::util::rng;
 
 use iter_fixed::IntoIteratorFixed;
 use std::cmp::Reverse;
@@ -184,9 +185,8 @@ impl EvalState for EvalStateV1 {
 
         empty_scores.sort_by_key(|s| Reverse(NonNan::new(*s.0).unwrap()));
         let mut counts_left: [usize; 4] = by_suit.into_iter_fixed().map(|v| v.len()).collect();
-        let mut rng = rand::thread_rng();
         for _ in 0..13 {
-            let sidx = rng.gen_range(0..4);
+            let sidx = rng::with_rng(|rng| rng.gen_range(0..4));
             if counts_left[sidx] > 0 {
                 counts_left[sidx] -= 1;
             } else {
@@ -1,6 +1,6 @@
 use crate::Card;
 use crate::game::actor::Actor;
-use crate::game::data::Move;
+use crate::game::data::{Move, PassDirection};
 
 pub mod basic_player_state;
 pub mod default_player_state;
@@ -19,6 +19,7 @@ pub trait BasicPlayerStateInterface: std::default::Default + Clone + std::fmt::D
 
     fn update_play_card(&mut self, played_moves: &Vec<Move>);
     fn update_did_play_card(&mut self, card: &Card);
+    fn update_did_pass(&mut self, passed_cards: &[Card], direction: PassDirection);
     fn update_end_round(&mut self, played_moves: &Vec<Move>, winner_pidx: usize);
     fn update_end_pass(&mut self, passed_cards: &Vec<Card>);
 }
@@ -29,6 +30,9 @@ pub trait DefaultPlayerStateInterface: BasicPlayerStateInterface {
     fn scored(&self) -> &[bool; 4];
     fn still_has(&self) -> &[[bool; 4]; 4];
     fn still_has_mut(&mut self) -> &mut [[bool; 4]; 4];
+    // The cards this player passed that have not been played yet, all held by `passed_pidx`.
+    fn passed_cards(&self) -> &Vec<Card>;
+    fn passed_pidx(&self) -> usize;
 
     fn final_scores(&self) -> [isize; 4];
 }
@@ -1,6 +1,6 @@
 use crate::{Card, Suit};
 use crate::rust_actors::player_state::BasicPlayerStateInterface;
-use crate::game::data::Move;
+use crate::game::data::{Move, PassDirection};
 
 #[derive(Clone, Debug)]
 pub struct BasicPlayerState {
@@ -63,6 +63,10 @@ impl BasicPlayerStateInterface for BasicPlayerState {
         self.cards.retain(|c| c != card);
     }
 
+    fn update_did_pass(&mut self, passed_cards: &[Card], _direction: PassDirection) {
+        self.cards.retain(|c| !passed_cards.contains(c));
+    }
+
     fn update_end_round(&mut self, played_moves: &Vec<Move>, _winner_pidx: usize) {
         self.first_round = false;
         if played_moves.into_iter().any(|m| m.card().suit() == Suit::Hearts) {
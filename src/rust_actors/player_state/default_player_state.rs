@@ -1,7 +1,7 @@
 use crate::Card;
 use crate::rust_actors::player_state::{BasicPlayerStateInterface, DefaultPlayerStateInterface};
 use crate::rust_actors::player_state::basic_player_state::BasicPlayerState;
-use crate::game::data::Move;
+use crate::game::data::{Move, PassDirection};
 
 #[derive(Clone, Debug)]
 pub struct DefaultPlayerState {
@@ -11,11 +11,13 @@ pub struct DefaultPlayerState {
     scores: [isize; 4],
     scored: [bool; 4],
     still_has: [[bool; 4]; 4],
+    passed_cards: Vec<Card>,
+    passed_pidx: usize,
 }
 
 impl DefaultPlayerState {
     pub fn new(cards: Vec<Card>, first_round: bool, hearts_played: bool, pidx: usize, cards_in_game: [[bool; 13]; 4], scores: [isize; 4], scored: [bool; 4], still_has: [[bool; 4]; 4]) -> Self {
-        Self { basic_player_state: BasicPlayerState::new(cards, first_round, hearts_played, pidx), cards_in_game, scores, scored, still_has }
+        Self { basic_player_state: BasicPlayerState::new(cards, first_round, hearts_played, pidx), cards_in_game, scores, scored, still_has, passed_cards: vec![], passed_pidx: pidx }
     }
 
     fn update_moves(&mut self, moves: &Vec<Move>) {
@@ -24,9 +26,11 @@ impl DefaultPlayerState {
             for Move(pidx, Card(rank, suit)) in moves {
                 // println!("strike: {:?} {:?}", rank, suit);
                 self.cards_in_game[suit.to_index()][rank.to_index()] = false;
-                if suit != first_suit { self.still_has[suit.to_index()][*pidx] = false; }
+                // Not following suit shows the player is out of the suit that was led.
+                if suit != first_suit { self.still_has[first_suit.to_index()][*pidx] = false; }
             }
         }
+        self.passed_cards.retain(|c| !moves.iter().any(|m| m.card() == *c));
     }
 }
 
@@ -77,6 +81,13 @@ impl BasicPlayerStateInterface for DefaultPlayerState {
         self.basic_player_state.update_did_play_card(card);
     }
 
+    fn update_did_pass(&mut self, passed_cards: &[Card], direction: PassDirection) {
+        self.basic_player_state.update_did_pass(passed_cards, direction);
+
+        self.passed_cards = passed_cards.to_vec();
+        self.passed_pidx = (self.pidx() + direction.index_shift()) % 4;
+    }
+
     fn update_end_round(&mut self, played_moves: &Vec<Move>, winner_pidx: usize) {
         self.basic_player_state.update_end_round(played_moves, winner_pidx);
 
@@ -112,6 +123,14 @@ impl DefaultPlayerStateInterface for DefaultPlayerState {
         &mut self.still_has
     }
 
+    fn passed_cards(&self) -> &Vec<Card> {
+        &self.passed_cards
+    }
+
+    fn passed_pidx(&self) -> usize {
+        self.passed_pidx
+    }
+
     fn final_scores(&self) -> [isize; 4] {
         self.scores.clone() // TODO: Should take 36 into account
     }
@@ -125,6 +144,8 @@ impl std::default::Default for DefaultPlayerState {
             scores: [0; 4],
             scored: [false; 4],
             still_has: [[true; 4]; 4],
+            passed_cards: vec![],
+            passed_pidx: 0,
         }
     }
 }
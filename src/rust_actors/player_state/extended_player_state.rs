@@ -1,7 +1,7 @@
 use crate::Card;
 use crate::rust_actors::player_state::{BasicPlayerStateInterface, DefaultPlayerStateInterface, ExtendedPlayerStateInterface};
 use crate::rust_actors::player_state::default_player_state::DefaultPlayerState;
-use crate::game::data::Move;
+use crate::game::data::{Move, PassDirection};
 
 #[derive(Clone, Debug)]
 pub struct ExtendedPlayerState {
@@ -54,6 +54,14 @@ impl DefaultPlayerStateInterface for ExtendedPlayerState {
         self.default_player_state.still_has_mut()
     }
 
+    fn passed_cards(&self) -> &Vec<Card> {
+        self.default_player_state.passed_cards()
+    }
+
+    fn passed_pidx(&self) -> usize {
+        self.default_player_state.passed_pidx()
+    }
+
     fn final_scores(&self) -> [isize; 4] {
         self.default_player_state.final_scores()
     }
@@ -110,6 +118,10 @@ impl BasicPlayerStateInterface for ExtendedPlayerState {
         self.default_player_state.update_did_play_card(card);
     }
 
+    fn update_did_pass(&mut self, passed_cards: &[Card], direction: PassDirection) {
+        self.default_player_state.update_did_pass(passed_cards, direction);
+    }
+
     fn update_end_round(&mut self, played_moves: &Vec<Move>, winner_pidx: usize) {
         self.default_player_state.update_end_round(played_moves, winner_pidx);
 
@@ -1,2 +1,6 @@
+pub mod deal_constraints;
+pub mod deals;
 pub mod deck;
 pub mod non_nan;
+pub mod platform;
+pub mod rng;
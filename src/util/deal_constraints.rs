@@ -0,0 +1,140 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::game::data::{Card, Hand, Suit};
+use crate::game::errors::HJError;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DealConstraint {
+    Holds(usize, Card),
+    Void(usize, Suit),
+    SuitAtMost(usize, Suit, usize),
+    SuitAtLeast(usize, Suit, usize),
+}
+
+impl DealConstraint {
+    // Parses `SEAT:holds:CARD`, `SEAT:void:SUIT`, `SEAT:max:SUIT:N` and `SEAT:min:SUIT:N`.
+    pub fn parse(string: &str) -> Result<Self, HJError> {
+        let invalid = || HJError::InvalidDealError(format!("Cannot parse deal constraint '{}'.", string));
+        let parts: Vec<&str> = string.split(':').collect();
+
+        let pidx = parts[0].parse::<usize>().ok().filter(|p| *p < 4).ok_or_else(invalid)?;
+        let suit = |part: Option<&&str>| part.and_then(|p| p.chars().next()).and_then(Suit::from_char).ok_or_else(invalid);
+        let count = |part: Option<&&str>| part.and_then(|p| p.parse::<usize>().ok()).ok_or_else(invalid);
+
+        match (parts.get(1).copied(), parts.len()) {
+            (Some("holds"), 3) => Ok(Self::Holds(pidx, parts[2].parse().map_err(|_| invalid())?)),
+            (Some("void"), 3) => Ok(Self::Void(pidx, suit(parts.get(2))?)),
+            (Some("max"), 4) => Ok(Self::SuitAtMost(pidx, suit(parts.get(2))?, count(parts.get(3))?)),
+            (Some("min"), 4) => Ok(Self::SuitAtLeast(pidx, suit(parts.get(2))?, count(parts.get(3))?)),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn is_satisfied(&self, hands: &[Vec<Card>; 4]) -> bool {
+        let suit_count = |pidx: usize, suit: Suit| hands[pidx].iter().filter(|c| c.suit() == suit).count();
+        match *self {
+            Self::Holds(pidx, card) => hands[pidx].contains(&card),
+            Self::Void(pidx, suit) => suit_count(pidx, suit) == 0,
+            Self::SuitAtMost(pidx, suit, count) => suit_count(pidx, suit) <= count,
+            Self::SuitAtLeast(pidx, suit, count) => suit_count(pidx, suit) >= count,
+        }
+    }
+}
+
+// Held cards are placed first and the rest is dealt uniformly, rejecting deals that break the
+// remaining constraints. Conditioning this way keeps the result uniform over all matching deals.
+pub fn get_constrained_hands<R: Rng>(constraints: &[DealConstraint], rng: &mut R, max_attempts: usize) -> Result<[Hand; 4], HJError> {
+    let mut fixed: [Vec<Card>; 4] = [vec![], vec![], vec![], vec![]];
+    for constraint in constraints {
+        if let DealConstraint::Holds(pidx, card) = constraint {
+            if fixed.iter().enumerate().any(|(p, cards)| p != *pidx && cards.contains(card)) {
+                return Err(HJError::InvalidDealError(format!("{:?} cannot be held by two players.", card)));
+            }
+            if !fixed[*pidx].contains(card) { fixed[*pidx].push(*card); }
+        }
+    }
+    if fixed.iter().any(|cards| cards.len() > 13) {
+        return Err(HJError::InvalidDealError("A player cannot hold more than 13 cards.".to_owned()));
+    }
+
+    let mut free_cards: Vec<Card> = Card::all().into_iter().filter(|c| !fixed.iter().any(|cards| cards.contains(c))).collect();
+    for _ in 0..max_attempts {
+        free_cards.shuffle(rng);
+
+        let mut remaining = free_cards.iter();
+        let hands = fixed.clone().map(|mut cards| {
+            let missing = 13 - cards.len();
+            cards.extend(remaining.by_ref().take(missing));
+            cards
+        });
+
+        if constraints.iter().all(|c| c.is_satisfied(&hands)) {
+            return Ok(hands.map(Hand::new));
+        }
+    }
+
+    Err(HJError::InvalidDealError(format!("No deal matching the constraints was found in {} attempts.", max_attempts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::game::data::Rank;
+    use crate::util::deals::check_hands;
+
+    #[test]
+    fn parses_every_form() {
+        let parse = |s| DealConstraint::parse(s).unwrap();
+        assert_eq!(parse("0:holds:QS"), DealConstraint::Holds(0, Card(Rank::Queen, Suit::Spades)));
+        assert_eq!(parse("1:holds:10h"), DealConstraint::Holds(1, Card(Rank::Ten, Suit::Hearts)));
+        assert_eq!(parse("3:void:H"), DealConstraint::Void(3, Suit::Hearts));
+        assert_eq!(parse("2:max:D:3"), DealConstraint::SuitAtMost(2, Suit::Diamonds, 3));
+        assert_eq!(parse("1:min:clubs:4"), DealConstraint::SuitAtLeast(1, Suit::Clubs, 4));
+    }
+
+    #[test]
+    fn rejects_malformed_specs() {
+        for spec in ["", "0", "x:void:H", "4:void:H", "0:owns:QS", "0:holds:ZZ", "0:holds", "0:void:X", "0:void:H:1", "0:max:D", "0:min:D:x", "0:max:D:3:4"] {
+            assert!(matches!(DealConstraint::parse(spec), Err(HJError::InvalidDealError(_))), "'{}' should be rejected", spec);
+        }
+    }
+
+    #[test]
+    fn samples_meet_their_constraints() {
+        let cases = [
+            vec!["0:holds:QS", "0:holds:AS", "1:void:S"],
+            vec!["2:void:H", "1:min:H:4"],
+            vec!["0:max:C:2", "1:max:C:3", "2:holds:2C", "3:min:D:4"],
+            vec!["0:holds:JD", "1:holds:QS", "2:holds:AH", "3:holds:2C"],
+        ];
+        let mut rng = StdRng::seed_from_u64(3);
+        for specs in cases {
+            let constraints: Vec<DealConstraint> = specs.iter().map(|s| DealConstraint::parse(s).unwrap()).collect();
+            for _ in 0..200 {
+                let hands = get_constrained_hands(&constraints, &mut rng, 10_000).expect("The constraints should be satisfiable.");
+                assert_eq!(check_hands(&hands), Ok(()));
+                let cards = hands.clone().map(|h| h.cards().clone());
+                for constraint in &constraints { assert!(constraint.is_satisfied(&cards), "{:?} in {:?}", constraint, cards); }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_impossible_constraints() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut deal = |specs: &[&str]| {
+            let constraints: Vec<DealConstraint> = specs.iter().map(|s| DealConstraint::parse(s).unwrap()).collect();
+            get_constrained_hands(&constraints, &mut rng, 100)
+        };
+        assert!(deal(&["0:holds:QS", "1:holds:QS"]).is_err());
+        let fourteen: Vec<String> = Rank::all().iter().map(|r| format!("0:holds:{}", Card(*r, Suit::Spades)))
+            .chain(["0:holds:2H".to_owned()])
+            .collect();
+        assert!(deal(&fourteen.iter().map(|s| s.as_str()).collect::<Vec<&str>>()).is_err());
+        assert!(deal(&["0:void:H", "1:void:H", "2:void:H", "3:void:H"]).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::game::data::{Card, Hand, PassDirection};
+use crate::game::errors::HJError;
+use crate::util::deal_constraints::{DealConstraint, get_constrained_hands};
+use crate::util::deck::get_seeded_hands;
+
+#[derive(Clone, Debug)]
+pub struct Deal {
+    id: String,
+    seed: Option<u64>,
+    hands: [Hand; 4],
+    pass_direction: PassDirection,
+}
+
+impl Deal {
+    pub fn new(id: String, seed: Option<u64>, hands: [Hand; 4], pass_direction: PassDirection) -> Self {
+        Self { id, seed, hands, pass_direction }
+    }
+
+    pub fn with_pass_direction(self, pass_direction: PassDirection) -> Self {
+        Self { pass_direction, ..self }
+    }
+
+    pub fn id(&self) -> &str { &self.id }
+
+    pub fn seed(&self) -> Option<u64> { self.seed }
+
+    pub fn hands(&self) -> &[Hand; 4] { &self.hands }
+
+    pub fn pass_direction(&self) -> PassDirection { self.pass_direction }
+
+    fn to_json(&self) -> json::JsonValue {
+        json::object!{
+            id: self.id.clone(),
+            seed: self.seed,
+            pass_direction: self.pass_direction.name(),
+            hands: self.hands.iter().map(|h| serialize_cards(h.cards())).collect::<Vec<Vec<Vec<usize>>>>(),
+        }
+    }
+
+    fn from_json(value: &json::JsonValue) -> Result<Self, HJError> {
+        let invalid = |reason: &str| HJError::InvalidDealError(format!("{} in deal {}", reason, value.dump()));
+
+        let id = value["id"].as_str().ok_or_else(|| invalid("Missing id"))?.to_owned();
+        let pass_direction = value["pass_direction"].as_str()
+            .and_then(PassDirection::from_name)
+            .ok_or_else(|| invalid("Invalid pass direction"))?;
+
+        let hands: Vec<Hand> = value["hands"].members()
+            .map(|hand| hand.members().map(deserialize_card).collect::<Option<Vec<Card>>>().map(Hand::new))
+            .collect::<Option<Vec<Hand>>>()
+            .ok_or_else(|| invalid("Invalid card"))?;
+        let hands: [Hand; 4] = hands.try_into().map_err(|_| invalid("Expected four hands"))?;
+        check_hands(&hands).map_err(invalid)?;
+
+        Ok(Self { id, seed: value["seed"].as_u64(), hands, pass_direction })
+    }
+}
+
+// Thirteen cards for every seat with no card twice, which leaves each of the 52 cards with one seat.
+pub fn check_hands(hands: &[Hand; 4]) -> Result<(), &'static str> {
+    let mut seen = [[false; 13]; 4];
+    for card in hands.iter().flat_map(|h| h.cards()) {
+        if seen[card.suit().to_index()][card.rank().to_index()] { return Err("Duplicate card"); }
+        seen[card.suit().to_index()][card.rank().to_index()] = true;
+    }
+    if hands.iter().any(|h| h.cards().len() != 13) { return Err("Expected 13 cards per hand"); }
+    Ok(())
+}
+
+pub fn generate_deals(count: usize, seed: u64) -> Vec<Deal> {
+    (0..count).map(|idx| {
+        let deal_seed = seed.wrapping_add(idx as u64);
+        Deal::new(format!("seed-{}", deal_seed), Some(deal_seed), get_seeded_hands(deal_seed), PassDirection::from_round(idx))
+    }).collect()
+}
+
+pub fn generate_constrained_deals(count: usize, seed: u64, constraints: &[DealConstraint], max_attempts: usize) -> Result<Vec<Deal>, HJError> {
+    (0..count).map(|idx| {
+        let deal_seed = seed.wrapping_add(idx as u64);
+        let hands = get_constrained_hands(constraints, &mut StdRng::seed_from_u64(deal_seed), max_attempts)?;
+        Ok(Deal::new(format!("constrained-{}", deal_seed), Some(deal_seed), hands, PassDirection::from_round(idx)))
+    }).collect()
+}
+
+pub fn save_deals(path: &str, deals: &[Deal]) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    for deal in deals { writeln!(file, "{}", json::stringify(deal.to_json()))?; }
+    file.flush()
+}
+
+pub fn load_deals(path: &str) -> Result<Vec<Deal>, HJError> {
+    let file = File::open(path).map_err(|e| HJError::InvalidDealError(format!("Cannot open {}: {}", path, e)))?;
+
+    BufReader::new(file).lines()
+        .map(|line| line.map_err(|e| HJError::InvalidDealError(format!("Cannot read {}: {}", path, e))))
+        .filter(|line| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|line| {
+            let line = line?;
+            let value = json::parse(&line).map_err(|e| HJError::InvalidDealError(format!("Invalid JSON in {}: {}", path, e)))?;
+            Deal::from_json(&value)
+        })
+        .collect()
+}
+
+fn serialize_cards(cards: &[Card]) -> Vec<Vec<usize>> {
+    cards.iter().map(|c| vec![c.rank() as usize, c.suit() as usize]).collect()
+}
+
+fn deserialize_card(value: &json::JsonValue) -> Option<Card> {
+    Some(Card::new(
+        num::FromPrimitive::from_u8(value[0].as_u8()?)?,
+        num::FromPrimitive::from_u8(value[1].as_u8()?)?,
+    ))
+}
@@ -1,13 +1,24 @@
 use crate::Card;
 use crate::game::data::{Hand, Move};
 
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 
+#[allow(dead_code)]
 pub fn get_shuffled_hands() -> [Hand; 4] {
+    deal_hands(&mut thread_rng())
+}
+
+pub fn get_seeded_hands(seed: u64) -> [Hand; 4] {
+    deal_hands(&mut StdRng::seed_from_u64(seed))
+}
+
+fn deal_hands<R: Rng>(rng: &mut R) -> [Hand; 4] {
     let mut cards = Card::all();
 
-    cards.shuffle(&mut thread_rng());
+    cards.shuffle(rng);
     cards.chunks(13)
          .map(move |cards| Hand::new(cards.to_vec()))
          .collect::<Vec<Hand>>()
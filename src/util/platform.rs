@@ -0,0 +1,31 @@
+// What differs on `wasm32-unknown-unknown`, which has no threads, no processes and no system clock.
+use std::thread;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub use web_time::Instant;
+
+pub struct Task<T> {
+    #[cfg(not(target_arch = "wasm32"))]
+    handle: thread::JoinHandle<T>,
+    #[cfg(target_arch = "wasm32")]
+    result: thread::Result<T>,
+}
+
+// Runs `work` on its own thread where there are threads, and right away otherwise.
+pub fn spawn<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(work: F) -> Task<T> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Task { handle: thread::spawn(work) };
+    #[cfg(target_arch = "wasm32")]
+    return Task { result: std::panic::catch_unwind(std::panic::AssertUnwindSafe(work)) };
+}
+
+impl<T> Task<T> {
+    pub fn join(self) -> thread::Result<T> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.handle.join();
+        #[cfg(target_arch = "wasm32")]
+        return self.result;
+    }
+}
@@ -0,0 +1,19 @@
+use std::cell::RefCell;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+// The random numbers behind the searches. Every thread has its own generator, seeded from the system
+// unless `seed` is called on that thread, so a benchmark can repeat the same random choices.
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+pub fn seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+// `f` must not draw through `with_rng` itself.
+pub fn with_rng<T, F: FnOnce(&mut StdRng) -> T>(f: F) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}